@@ -1,6 +1,9 @@
+use crate::value::PropertyValue;
+use crate::variants::{ThemeVariants, WidgetVariant};
 use egui::{Color32, Visuals};
+use std::collections::BTreeMap;
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct ThemeConfig {
     pub name: String,
     pub dark_mode: bool,
@@ -13,6 +16,14 @@ pub struct ThemeConfig {
     pub override_code_bg_color: Option<[u8; 4]>,
     pub override_warn_fg_color: Option<[u8; 4]>,
     pub override_error_fg_color: Option<[u8; 4]>,
+    pub override_success_fg_color: Option<[u8; 4]>,
+    pub override_success_bg_color: Option<[u8; 4]>,
+    pub override_info_fg_color: Option<[u8; 4]>,
+    pub override_info_bg_color: Option<[u8; 4]>,
+    pub override_accent_fg_color: Option<[u8; 4]>,
+    pub override_accent_bg_color: Option<[u8; 4]>,
+    pub override_muted_fg_color: Option<[u8; 4]>,
+    pub override_muted_bg_color: Option<[u8; 4]>,
 
     pub override_window_fill: Option<[u8; 4]>,
     pub override_window_stroke_color: Option<[u8; 4]>,
@@ -81,8 +92,33 @@ pub struct ThemeConfig {
     pub override_indent_has_left_vline: Option<bool>,
     pub override_striped: Option<bool>,
     pub override_slider_trailing_fill: Option<bool>,
+
+    #[serde(default)]
+    pub variants: ThemeVariants,
+
+    /// App-defined values that travel with the theme, such as a brand color or chart palette.
+    #[serde(default)]
+    pub custom_properties: BTreeMap<String, PropertyValue>,
+
+    /// Lowercase keywords such as `warm` or `pastel`, used to filter the theme gallery.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeValidationError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl std::fmt::Display for ThemeValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid value for `{}`: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for ThemeValidationError {}
+
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
@@ -96,6 +132,14 @@ impl Default for ThemeConfig {
             override_code_bg_color: None,
             override_warn_fg_color: None,
             override_error_fg_color: None,
+            override_success_fg_color: None,
+            override_success_bg_color: None,
+            override_info_fg_color: None,
+            override_info_bg_color: None,
+            override_accent_fg_color: None,
+            override_accent_bg_color: None,
+            override_muted_fg_color: None,
+            override_muted_bg_color: None,
             override_window_fill: None,
             override_window_stroke_color: None,
             override_window_stroke_width: None,
@@ -154,6 +198,9 @@ impl Default for ThemeConfig {
             override_indent_has_left_vline: None,
             override_striped: None,
             override_slider_trailing_fill: None,
+            variants: ThemeVariants::default(),
+            custom_properties: BTreeMap::new(),
+            tags: Vec::new(),
         }
     }
 }
@@ -163,6 +210,15 @@ impl ThemeConfig {
         Self {
             name: "Dark".to_string(),
             dark_mode: true,
+            override_success_fg_color: Some([120, 200, 120, 255]),
+            override_success_bg_color: Some([30, 60, 35, 255]),
+            override_info_fg_color: Some([100, 170, 240, 255]),
+            override_info_bg_color: Some([25, 45, 70, 255]),
+            override_accent_fg_color: Some([90, 160, 255, 255]),
+            override_accent_bg_color: Some([30, 50, 80, 255]),
+            override_muted_fg_color: Some([140, 140, 140, 255]),
+            override_muted_bg_color: Some([45, 45, 45, 255]),
+            tags: tags(&["classic"]),
             ..Default::default()
         }
     }
@@ -171,6 +227,15 @@ impl ThemeConfig {
         Self {
             name: "Light".to_string(),
             dark_mode: false,
+            override_success_fg_color: Some([30, 130, 60, 255]),
+            override_success_bg_color: Some([220, 240, 222, 255]),
+            override_info_fg_color: Some([30, 100, 200, 255]),
+            override_info_bg_color: Some([220, 232, 250, 255]),
+            override_accent_fg_color: Some([0, 110, 200, 255]),
+            override_accent_bg_color: Some([215, 235, 250, 255]),
+            override_muted_fg_color: Some([110, 110, 110, 255]),
+            override_muted_bg_color: Some([235, 235, 235, 255]),
+            tags: tags(&["classic"]),
             ..Default::default()
         }
     }
@@ -189,6 +254,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([68, 71, 90, 255]),
             override_warn_fg_color: Some([241, 250, 140, 255]),
             override_error_fg_color: Some([255, 85, 85, 255]),
+            override_success_fg_color: Some([80, 250, 123, 255]),
+            override_success_bg_color: Some([45, 70, 60, 255]),
+            override_info_fg_color: Some([139, 233, 253, 255]),
+            override_info_bg_color: Some([50, 70, 80, 255]),
+            override_accent_fg_color: Some([189, 147, 249, 255]),
+            override_accent_bg_color: Some([65, 58, 90, 255]),
+            override_muted_fg_color: Some([98, 114, 164, 255]),
+            override_muted_bg_color: Some([55, 58, 75, 255]),
+            tags: tags(&["vibrant", "purple"]),
             ..Default::default()
         }
     }
@@ -207,6 +281,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([59, 66, 82, 255]),
             override_warn_fg_color: Some([235, 203, 139, 255]),
             override_error_fg_color: Some([191, 97, 106, 255]),
+            override_success_fg_color: Some([163, 190, 140, 255]),
+            override_success_bg_color: Some([62, 72, 70, 255]),
+            override_info_fg_color: Some([129, 161, 193, 255]),
+            override_info_bg_color: Some([55, 66, 82, 255]),
+            override_accent_fg_color: Some([136, 192, 208, 255]),
+            override_accent_bg_color: Some([58, 75, 88, 255]),
+            override_muted_fg_color: Some([120, 130, 150, 255]),
+            override_muted_bg_color: Some([59, 66, 82, 255]),
+            tags: tags(&["cool", "muted"]),
             ..Default::default()
         }
     }
@@ -225,6 +308,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([60, 56, 54, 255]),
             override_warn_fg_color: Some([250, 189, 47, 255]),
             override_error_fg_color: Some([251, 73, 52, 255]),
+            override_success_fg_color: Some([184, 187, 38, 255]),
+            override_success_bg_color: Some([60, 62, 35, 255]),
+            override_info_fg_color: Some([131, 165, 152, 255]),
+            override_info_bg_color: Some([48, 56, 58, 255]),
+            override_accent_fg_color: Some([254, 128, 25, 255]),
+            override_accent_bg_color: Some([70, 50, 30, 255]),
+            override_muted_fg_color: Some([146, 131, 116, 255]),
+            override_muted_bg_color: Some([50, 48, 46, 255]),
+            tags: tags(&["warm", "retro"]),
             ..Default::default()
         }
     }
@@ -243,6 +335,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([7, 54, 66, 255]),
             override_warn_fg_color: Some([181, 137, 0, 255]),
             override_error_fg_color: Some([220, 50, 47, 255]),
+            override_success_fg_color: Some([133, 153, 0, 255]),
+            override_success_bg_color: Some([20, 60, 40, 255]),
+            override_info_fg_color: Some([38, 139, 210, 255]),
+            override_info_bg_color: Some([8, 60, 85, 255]),
+            override_accent_fg_color: Some([108, 113, 196, 255]),
+            override_accent_bg_color: Some([25, 55, 80, 255]),
+            override_muted_fg_color: Some([88, 110, 117, 255]),
+            override_muted_bg_color: Some([7, 54, 66, 255]),
+            tags: tags(&["muted", "low-contrast"]),
             ..Default::default()
         }
     }
@@ -261,6 +362,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([238, 232, 213, 255]),
             override_warn_fg_color: Some([181, 137, 0, 255]),
             override_error_fg_color: Some([220, 50, 47, 255]),
+            override_success_fg_color: Some([133, 153, 0, 255]),
+            override_success_bg_color: Some([232, 236, 200, 255]),
+            override_info_fg_color: Some([38, 139, 210, 255]),
+            override_info_bg_color: Some([222, 235, 240, 255]),
+            override_accent_fg_color: Some([108, 113, 196, 255]),
+            override_accent_bg_color: Some([230, 230, 240, 255]),
+            override_muted_fg_color: Some([147, 161, 161, 255]),
+            override_muted_bg_color: Some([238, 232, 213, 255]),
+            tags: tags(&["muted", "low-contrast"]),
             ..Default::default()
         }
     }
@@ -279,6 +389,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([73, 72, 62, 255]),
             override_warn_fg_color: Some([230, 219, 116, 255]),
             override_error_fg_color: Some([249, 38, 114, 255]),
+            override_success_fg_color: Some([166, 226, 46, 255]),
+            override_success_bg_color: Some([55, 68, 35, 255]),
+            override_info_fg_color: Some([102, 217, 239, 255]),
+            override_info_bg_color: Some([45, 65, 70, 255]),
+            override_accent_fg_color: Some([174, 129, 255, 255]),
+            override_accent_bg_color: Some([58, 50, 75, 255]),
+            override_muted_fg_color: Some([117, 113, 94, 255]),
+            override_muted_bg_color: Some([55, 55, 48, 255]),
+            tags: tags(&["vibrant", "retro"]),
             ..Default::default()
         }
     }
@@ -297,6 +416,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([33, 37, 43, 255]),
             override_warn_fg_color: Some([229, 192, 123, 255]),
             override_error_fg_color: Some([224, 108, 117, 255]),
+            override_success_fg_color: Some([152, 195, 121, 255]),
+            override_success_bg_color: Some([45, 60, 45, 255]),
+            override_info_fg_color: Some([97, 175, 239, 255]),
+            override_info_bg_color: Some([38, 55, 72, 255]),
+            override_accent_fg_color: Some([198, 120, 221, 255]),
+            override_accent_bg_color: Some([58, 45, 65, 255]),
+            override_muted_fg_color: Some([92, 99, 112, 255]),
+            override_muted_bg_color: Some([44, 48, 56, 255]),
+            tags: tags(&["cool", "muted"]),
             ..Default::default()
         }
     }
@@ -315,6 +443,15 @@ impl ThemeConfig {
             override_code_bg_color: Some([36, 40, 59, 255]),
             override_warn_fg_color: Some([224, 175, 104, 255]),
             override_error_fg_color: Some([247, 118, 142, 255]),
+            override_success_fg_color: Some([158, 206, 106, 255]),
+            override_success_bg_color: Some([40, 55, 45, 255]),
+            override_info_fg_color: Some([125, 207, 255, 255]),
+            override_info_bg_color: Some([35, 50, 70, 255]),
+            override_accent_fg_color: Some([187, 154, 247, 255]),
+            override_accent_bg_color: Some([50, 45, 75, 255]),
+            override_muted_fg_color: Some([86, 95, 137, 255]),
+            override_muted_bg_color: Some([41, 46, 66, 255]),
+            tags: tags(&["cool", "vibrant"]),
             ..Default::default()
         }
     }
@@ -333,10 +470,31 @@ impl ThemeConfig {
             override_code_bg_color: Some([49, 50, 68, 255]),
             override_warn_fg_color: Some([249, 226, 175, 255]),
             override_error_fg_color: Some([243, 139, 168, 255]),
+            override_success_fg_color: Some([166, 227, 161, 255]),
+            override_success_bg_color: Some([45, 60, 55, 255]),
+            override_info_fg_color: Some([137, 180, 250, 255]),
+            override_info_bg_color: Some([45, 55, 80, 255]),
+            override_accent_fg_color: Some([203, 166, 247, 255]),
+            override_accent_bg_color: Some([60, 50, 80, 255]),
+            override_muted_fg_color: Some([108, 112, 134, 255]),
+            override_muted_bg_color: Some([49, 50, 68, 255]),
+            tags: tags(&["pastel", "warm"]),
             ..Default::default()
         }
     }
 
+    pub fn custom_property(&self, key: &str) -> Option<&PropertyValue> {
+        self.custom_properties.get(key)
+    }
+
+    pub fn set_custom_property(&mut self, key: impl Into<String>, value: impl Into<PropertyValue>) {
+        self.custom_properties.insert(key.into(), value.into());
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|own| own.eq_ignore_ascii_case(tag))
+    }
+
     pub fn all_presets() -> Vec<Self> {
         vec![
             Self::dark_preset(),
@@ -662,14 +820,143 @@ impl ThemeConfig {
         visuals
     }
 
+    /// Checks that every numeric override is finite and within the range egui can render.
+    pub fn validate(&self) -> Result<(), ThemeValidationError> {
+        let non_negative = [
+            (
+                "override_window_stroke_width",
+                self.override_window_stroke_width,
+            ),
+            (
+                "override_selection_stroke_width",
+                self.override_selection_stroke_width,
+            ),
+            (
+                "override_widget_noninteractive_bg_stroke_width",
+                self.override_widget_noninteractive_bg_stroke_width,
+            ),
+            (
+                "override_widget_noninteractive_fg_stroke_width",
+                self.override_widget_noninteractive_fg_stroke_width,
+            ),
+            (
+                "override_widget_inactive_bg_stroke_width",
+                self.override_widget_inactive_bg_stroke_width,
+            ),
+            (
+                "override_widget_inactive_fg_stroke_width",
+                self.override_widget_inactive_fg_stroke_width,
+            ),
+            (
+                "override_widget_hovered_bg_stroke_width",
+                self.override_widget_hovered_bg_stroke_width,
+            ),
+            (
+                "override_widget_hovered_fg_stroke_width",
+                self.override_widget_hovered_fg_stroke_width,
+            ),
+            (
+                "override_widget_active_bg_stroke_width",
+                self.override_widget_active_bg_stroke_width,
+            ),
+            (
+                "override_widget_active_fg_stroke_width",
+                self.override_widget_active_fg_stroke_width,
+            ),
+            (
+                "override_widget_open_bg_stroke_width",
+                self.override_widget_open_bg_stroke_width,
+            ),
+            (
+                "override_widget_open_fg_stroke_width",
+                self.override_widget_open_fg_stroke_width,
+            ),
+            (
+                "override_resize_corner_size",
+                self.override_resize_corner_size,
+            ),
+            (
+                "override_text_cursor_width",
+                self.override_text_cursor_width,
+            ),
+            ("override_clip_rect_margin", self.override_clip_rect_margin),
+        ];
+        for (field, value) in non_negative {
+            if let Some(value) = value {
+                if !value.is_finite() || value < 0.0 {
+                    return Err(ThemeValidationError {
+                        field,
+                        message: format!(
+                            "expected a finite, non-negative number but found {value}"
+                        ),
+                    });
+                }
+            }
+        }
+
+        for variant in WidgetVariant::ALL {
+            let style = self.variants.get(variant);
+            for state in [&style.inactive, &style.hovered, &style.active] {
+                if let Some(width) = state.stroke_width {
+                    if !width.is_finite() || width < 0.0 {
+                        return Err(ThemeValidationError {
+                            field: "variants.stroke_width",
+                            message: format!(
+                                "{} variant: expected a finite, non-negative number but found {width}",
+                                variant.label()
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        let finite = [
+            (
+                "override_widget_noninteractive_expansion",
+                self.override_widget_noninteractive_expansion,
+            ),
+            (
+                "override_widget_inactive_expansion",
+                self.override_widget_inactive_expansion,
+            ),
+            (
+                "override_widget_hovered_expansion",
+                self.override_widget_hovered_expansion,
+            ),
+            (
+                "override_widget_active_expansion",
+                self.override_widget_active_expansion,
+            ),
+            (
+                "override_widget_open_expansion",
+                self.override_widget_open_expansion,
+            ),
+        ];
+        for (field, value) in finite {
+            if let Some(value) = value {
+                if !value.is_finite() {
+                    return Err(ThemeValidationError {
+                        field,
+                        message: format!("expected a finite number but found {value}"),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the theme as JSON. On the web `path` is a key in `localStorage`,
+    /// see [`crate::LocalStorage`].
     pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), std::io::Error> {
         let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, json)?;
+        crate::fs::write(path, &json)?;
         Ok(())
     }
 
     pub fn load_from_file(path: &std::path::Path) -> Result<Self, Box<dyn std::error::Error>> {
-        let json = std::fs::read_to_string(path)?;
+        let json = crate::fs::read_to_string(path)?;
         let config = serde_json::from_str(&json)?;
         Ok(config)
     }
@@ -762,7 +1049,19 @@ impl ThemeConfig {
             override_code_bg_color: Some(random_color(&mut rng)),
             override_warn_fg_color: Some(random_color(&mut rng)),
             override_error_fg_color: Some(random_color(&mut rng)),
+            override_success_fg_color: Some(random_color(&mut rng)),
+            override_success_bg_color: Some(random_color(&mut rng)),
+            override_info_fg_color: Some(random_color(&mut rng)),
+            override_info_bg_color: Some(random_color(&mut rng)),
+            override_accent_fg_color: Some(random_color(&mut rng)),
+            override_accent_bg_color: Some(random_color(&mut rng)),
+            override_muted_fg_color: Some(random_color(&mut rng)),
+            override_muted_bg_color: Some(random_color(&mut rng)),
             ..Default::default()
         }
     }
 }
+
+fn tags(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|tag| tag.to_string()).collect()
+}
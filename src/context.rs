@@ -0,0 +1,122 @@
+use crate::config::ThemeConfig;
+use crate::semantic::SemanticColors;
+use crate::value::PropertyValue;
+use std::sync::Arc;
+
+const ACTIVE_THEME_KEY: &str = "egui_thematic_active_theme_config";
+
+/// The theme most recently applied with [`ThemeContextExt::set_theme_config`],
+/// together with the values resolved from it.
+#[derive(Clone, Debug)]
+pub struct ActiveTheme {
+    pub config: ThemeConfig,
+    pub visuals: egui::Visuals,
+    pub semantic_colors: SemanticColors,
+}
+
+impl ActiveTheme {
+    pub fn new(config: ThemeConfig) -> Self {
+        let visuals = config.to_visuals();
+        let semantic_colors = config.semantic_colors_with(&visuals);
+        Self {
+            config,
+            visuals,
+            semantic_colors,
+        }
+    }
+}
+
+/// Reads and sets the active theme through an [`egui::Context`] or [`egui::Ui`].
+///
+/// [`crate::render_theme_panel`], [`crate::ThemeStack::apply`] and
+/// [`crate::ThemeFileWatcher::update`] keep the active theme up to date, so app
+/// code anywhere in the UI can look it up without access to the editor state.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use egui_thematic::{ThemeConfig, ThemeContextExt};
+///
+/// ctx.set_theme_config(ThemeConfig::nord_preset());
+///
+/// // Anywhere else, e.g. deep inside a widget
+/// let colors = ui.semantic_colors();
+/// ui.colored_label(colors.success_fg_color, "Connected");
+///
+/// if let Some(brand) = ui.theme_property("brand").and_then(|value| value.as_color32()) {
+///     ui.colored_label(brand, "Acme");
+/// }
+/// ```
+pub trait ThemeContextExt {
+    /// The active theme, or `None` if no theme has been set through this crate yet.
+    fn active_theme(&self) -> Option<Arc<ActiveTheme>>;
+
+    /// Applies `config` to the context and stores it as the active theme.
+    ///
+    /// Does nothing if `config` is already the active theme.
+    fn set_theme_config(&self, config: ThemeConfig);
+
+    fn active_theme_config(&self) -> Option<ThemeConfig> {
+        self.active_theme().map(|active| active.config.clone())
+    }
+
+    fn active_theme_name(&self) -> Option<String> {
+        self.active_theme().map(|active| active.config.name.clone())
+    }
+
+    /// Semantic colors of the active theme, or ones derived from the current
+    /// visuals if no theme has been set.
+    fn semantic_colors(&self) -> SemanticColors;
+
+    /// A custom property of the active theme.
+    fn theme_property(&self, key: &str) -> Option<PropertyValue> {
+        self.active_theme()?.config.custom_property(key).cloned()
+    }
+}
+
+impl ThemeContextExt for egui::Context {
+    fn active_theme(&self) -> Option<Arc<ActiveTheme>> {
+        self.data(|data| data.get_temp::<Arc<ActiveTheme>>(egui::Id::new(ACTIVE_THEME_KEY)))
+    }
+
+    fn set_theme_config(&self, config: ThemeConfig) {
+        if self
+            .active_theme()
+            .is_some_and(|active| active.config == config)
+        {
+            return;
+        }
+        let active = ActiveTheme::new(config);
+        self.set_visuals(active.visuals.clone());
+        self.data_mut(|data| data.insert_temp(egui::Id::new(ACTIVE_THEME_KEY), Arc::new(active)));
+        self.request_repaint();
+    }
+
+    fn semantic_colors(&self) -> SemanticColors {
+        match self.active_theme() {
+            Some(active) => active.semantic_colors,
+            None => {
+                let visuals = self.style().visuals.clone();
+                let config = ThemeConfig {
+                    dark_mode: visuals.dark_mode,
+                    ..Default::default()
+                };
+                config.semantic_colors_with(&visuals)
+            }
+        }
+    }
+}
+
+impl ThemeContextExt for egui::Ui {
+    fn active_theme(&self) -> Option<Arc<ActiveTheme>> {
+        self.ctx().active_theme()
+    }
+
+    fn set_theme_config(&self, config: ThemeConfig) {
+        self.ctx().set_theme_config(config);
+    }
+
+    fn semantic_colors(&self) -> SemanticColors {
+        self.ctx().semantic_colors()
+    }
+}
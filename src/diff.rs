@@ -0,0 +1,343 @@
+use crate::config::ThemeConfig;
+use crate::value::PropertyValue;
+use std::collections::BTreeMap;
+
+/// Fields that identify a theme rather than style it, left out of [`ThemeDiff::to_patch`].
+const IDENTITY_KEYS: &[&str] = &["name", "dark_mode"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyChange {
+    /// The property is overridden in the new theme but not in the old one.
+    Added { new: PropertyValue },
+    /// The property is overridden in the old theme but not in the new one.
+    Removed { old: PropertyValue },
+    Changed {
+        old: PropertyValue,
+        new: PropertyValue,
+    },
+}
+
+impl PropertyChange {
+    pub fn old_value(&self) -> Option<&PropertyValue> {
+        match self {
+            PropertyChange::Added { .. } => None,
+            PropertyChange::Removed { old } | PropertyChange::Changed { old, .. } => Some(old),
+        }
+    }
+
+    pub fn new_value(&self) -> Option<&PropertyValue> {
+        match self {
+            PropertyChange::Removed { .. } => None,
+            PropertyChange::Added { new } | PropertyChange::Changed { new, .. } => Some(new),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyDiff {
+    pub key: String,
+    pub change: PropertyChange,
+}
+
+/// The per-property differences between two themes.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_thematic::ThemeConfig;
+///
+/// let dracula = ThemeConfig::dracula_preset();
+/// let mut custom = dracula.clone();
+/// custom.override_panel_fill = Some([20, 20, 30, 255]);
+///
+/// let diff = dracula.diff(&custom);
+/// println!("{}", diff.changelog());
+///
+/// let mut nord = ThemeConfig::nord_preset();
+/// diff.to_patch().apply(&mut nord).unwrap();
+/// assert_eq!(nord.override_panel_fill, Some([20, 20, 30, 255]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeDiff {
+    pub from_name: String,
+    pub to_name: String,
+    pub properties: Vec<PropertyDiff>,
+}
+
+impl ThemeDiff {
+    pub fn between(from: &ThemeConfig, to: &ThemeConfig) -> Self {
+        let from_fields = config_fields(from);
+        let to_fields = config_fields(to);
+
+        let keys: std::collections::BTreeSet<&String> =
+            from_fields.keys().chain(to_fields.keys()).collect();
+        let properties = keys
+            .into_iter()
+            .filter_map(|key| {
+                let old = from_fields.get(key).cloned().flatten();
+                let new = to_fields.get(key).cloned().flatten();
+                let change = match (old, new) {
+                    (None, Some(new)) => PropertyChange::Added { new },
+                    (Some(old), None) => PropertyChange::Removed { old },
+                    (Some(old), Some(new)) if old != new => PropertyChange::Changed { old, new },
+                    _ => return None,
+                };
+                Some(PropertyDiff {
+                    key: key.clone(),
+                    change,
+                })
+            })
+            .collect();
+
+        Self {
+            from_name: from.name.clone(),
+            to_name: to.name.clone(),
+            properties,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PropertyChange> {
+        self.properties
+            .iter()
+            .find(|property| property.key == key)
+            .map(|property| &property.change)
+    }
+
+    /// A patch that turns the `from` theme into the `to` theme.
+    ///
+    /// Like [`ThemeConfig::merge`], the patch leaves the name and dark mode of
+    /// the theme it is applied to alone, so it can be carried over to another
+    /// theme. Use [`Self::to_full_patch`] to include them.
+    pub fn to_patch(&self) -> ThemePatch {
+        self.patch_where(|key| !IDENTITY_KEYS.contains(&key))
+    }
+
+    /// A patch with every change, the name and dark mode included.
+    pub fn to_full_patch(&self) -> ThemePatch {
+        self.patch_where(|_| true)
+    }
+
+    fn patch_where(&self, include: impl Fn(&str) -> bool) -> ThemePatch {
+        ThemePatch {
+            changes: self
+                .properties
+                .iter()
+                .filter(|property| include(&property.key))
+                .map(|property| (property.key.clone(), property.change.new_value().cloned()))
+                .collect(),
+        }
+    }
+
+    /// A plain-text summary with one line per changed property.
+    pub fn changelog(&self) -> String {
+        let mut changelog = format!(
+            "Changes from \"{}\" to \"{}\":\n",
+            self.from_name, self.to_name
+        );
+        if self.properties.is_empty() {
+            changelog.push_str("  (no changes)\n");
+        }
+        for property in &self.properties {
+            let line = match &property.change {
+                PropertyChange::Added { new } => format!("  + {}: {new}\n", property.key),
+                PropertyChange::Removed { old } => {
+                    format!("  - {} (was {old})\n", property.key)
+                }
+                PropertyChange::Changed { old, new } => {
+                    format!("  ~ {}: {old} -> {new}\n", property.key)
+                }
+            };
+            changelog.push_str(&line);
+        }
+        changelog
+    }
+}
+
+#[derive(Debug)]
+pub enum PatchError {
+    UnknownProperty(String),
+    InvalidValue(serde_json::Error),
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchError::UnknownProperty(key) => write!(f, "unknown theme property '{key}'"),
+            PatchError::InvalidValue(error) => write!(f, "invalid patch value: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+/// A set of property assignments that can be saved and applied to any theme.
+///
+/// Keys are field names, with nested fields joined by dots such as
+/// `variants.primary.hovered.fill`. Custom properties use the
+/// `custom_properties.<key>` form and can be added as well as changed.
+///
+/// A `None` value removes the override so the egui default is used again, or
+/// clears the tags.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ThemePatch {
+    pub changes: BTreeMap<String, Option<PropertyValue>>,
+}
+
+impl ThemePatch {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn apply(&self, config: &mut ThemeConfig) -> Result<(), PatchError> {
+        let mut value = serde_json::to_value(&*config).map_err(PatchError::InvalidValue)?;
+        if let Some(fields) = value.as_object_mut() {
+            // Empty tags are left out of the JSON but can still be patched.
+            fields
+                .entry("tags")
+                .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        }
+        for (key, change) in &self.changes {
+            if let Some(name) = key.strip_prefix("custom_properties.") {
+                let properties = value
+                    .get_mut("custom_properties")
+                    .and_then(serde_json::Value::as_object_mut)
+                    .ok_or_else(|| PatchError::UnknownProperty(key.clone()))?;
+                match change {
+                    Some(change) => {
+                        properties.insert(name.to_string(), change.to_json());
+                    }
+                    None => {
+                        properties.remove(name);
+                    }
+                }
+                continue;
+            }
+            let field = key
+                .split('.')
+                .try_fold(&mut value, |value, segment| value.get_mut(segment))
+                .filter(|field| !field.is_object());
+            let Some(field) = field else {
+                return Err(PatchError::UnknownProperty(key.clone()));
+            };
+            *field = match change {
+                Some(change) => change.to_json(),
+                None if field.is_array() => serde_json::Value::Array(Vec::new()),
+                None => serde_json::Value::Null,
+            };
+        }
+        *config = serde_json::from_value(value).map_err(PatchError::InvalidValue)?;
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+fn config_fields(config: &ThemeConfig) -> BTreeMap<String, Option<PropertyValue>> {
+    let mut fields = BTreeMap::new();
+    if let Ok(value) = serde_json::to_value(config) {
+        flatten_fields(&value, "", &mut fields);
+    }
+    fields
+}
+
+fn flatten_fields(
+    value: &serde_json::Value,
+    prefix: &str,
+    fields: &mut BTreeMap<String, Option<PropertyValue>>,
+) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_fields(value, &path, fields);
+            }
+        }
+        value => {
+            fields.insert(prefix.to_string(), PropertyValue::from_json(value));
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// The differences from `self` to `other`.
+    pub fn diff(&self, other: &ThemeConfig) -> ThemeDiff {
+        ThemeDiff::between(self, other)
+    }
+
+    pub fn apply_patch(&mut self, patch: &ThemePatch) -> Result<(), PatchError> {
+        patch.apply(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_edits_are_diffed_and_patched() {
+        let nord = ThemeConfig::nord_preset();
+        let mut tagged = nord.clone();
+        tagged.tags.push("focus".to_string());
+
+        let diff = nord.diff(&tagged);
+        assert_eq!(
+            diff.get("tags"),
+            Some(&PropertyChange::Changed {
+                old: PropertyValue::List(nord.tags.clone()),
+                new: PropertyValue::List(tagged.tags.clone()),
+            })
+        );
+        assert!(diff.changelog().contains("tags"));
+
+        let mut patched = nord.clone();
+        diff.to_patch().apply(&mut patched).unwrap();
+        assert_eq!(patched, tagged);
+
+        let mut untagged = nord.clone();
+        untagged.tags.clear();
+        let mut patched = nord.clone();
+        nord.diff(&untagged).to_patch().apply(&mut patched).unwrap();
+        assert!(patched.tags.is_empty());
+    }
+
+    #[test]
+    fn patch_carries_over_to_another_theme() {
+        let dracula = ThemeConfig::dracula_preset();
+        let mut custom = dracula.clone();
+        custom.name = "My Dracula".to_string();
+        custom.dark_mode = false;
+        custom.override_panel_fill = Some([20, 20, 30, 255]);
+
+        let diff = dracula.diff(&custom);
+        assert!(diff.get("name").is_some());
+
+        let mut nord = ThemeConfig::nord_preset();
+        diff.to_patch().apply(&mut nord).unwrap();
+        assert_eq!(nord.name, ThemeConfig::nord_preset().name);
+        assert!(nord.dark_mode);
+        assert_eq!(nord.override_panel_fill, Some([20, 20, 30, 255]));
+
+        let mut renamed = ThemeConfig::nord_preset();
+        diff.to_full_patch().apply(&mut renamed).unwrap();
+        assert_eq!(renamed.name, "My Dracula");
+        assert!(!renamed.dark_mode);
+    }
+}
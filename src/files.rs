@@ -0,0 +1,382 @@
+use crate::config::ThemeConfig;
+use crate::response::ThemeEditorEvent;
+use crate::state::ThemeEditorState;
+use std::path::{Path, PathBuf};
+
+/// What happens to theme files dropped onto the app, see
+/// [`ThemeEditorState::file_drop`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThemeFileDrop {
+    /// Opens the file as the current theme, like the Open button.
+    Apply,
+    /// Registers the theme as a user preset without switching to it.
+    AddToRegistry,
+}
+
+/// A file operation that replaces the current theme, held back while the
+/// user decides whether to discard unsaved changes.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum FileAction {
+    Open,
+    OpenRecent(PathBuf),
+    /// A theme file dropped onto the app, already loaded.
+    OpenDropped {
+        config: Box<ThemeConfig>,
+        path: PathBuf,
+    },
+}
+
+/// A browser file dialog whose result arrives on a later frame.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct FileDialogTask {
+    receiver: std::sync::mpsc::Receiver<FileDialogResult>,
+}
+
+#[cfg(target_arch = "wasm32")]
+enum FileDialogResult {
+    Opened {
+        name: String,
+        contents: Vec<u8>,
+    },
+    Saved {
+        name: String,
+        config: Box<ThemeConfig>,
+    },
+    Failed(String),
+}
+
+impl ThemeEditorState {
+    pub const DEFAULT_MAX_RECENT_FILES: usize = 10;
+
+    /// Whether the theme changed since it was last opened or saved.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.current_config != self.saved_config
+    }
+
+    /// Replaces the current theme with one loaded from `path`, as an undoable step.
+    ///
+    /// Fails without changing anything if the file does not pass
+    /// [`ThemeConfig::validate`].
+    pub fn load_theme_file(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let config = ThemeConfig::load_from_file(path)?;
+        config.validate()?;
+        self.finish_open(config, path.to_path_buf());
+        Ok(())
+    }
+
+    pub fn save_theme_file(&mut self, path: &Path) -> Result<(), std::io::Error> {
+        self.current_config.save_to_file(path)?;
+        self.finish_save(path.to_path_buf(), self.current_config.clone());
+        Ok(())
+    }
+
+    /// Shows an open dialog and loads the picked theme.
+    ///
+    /// On the web the dialog is asynchronous and the theme is loaded by
+    /// [`crate::render_theme_editor`] once the user picked a file.
+    pub fn open_file_dialog(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = theme_file_dialog().pick_file() {
+            let result = self.load_theme_file(&path);
+            self.file_error = result
+                .err()
+                .map(|error| format!("Failed to open {}: {error}", path.display()));
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let dialog = theme_file_dialog();
+            self.spawn_file_dialog(async move {
+                let file = dialog.pick_file().await?;
+                Some(FileDialogResult::Opened {
+                    name: file.file_name(),
+                    contents: file.read().await,
+                })
+            });
+        }
+    }
+
+    /// Saves to [`Self::current_file`], asking for a location if there is none.
+    ///
+    /// On the web the theme is saved to `localStorage` under the current file
+    /// name without asking; use [`Self::save_as_dialog`] to download it.
+    pub fn save(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let path = match self.current_file.clone() {
+            Some(path) => path,
+            None => return self.save_as_dialog(),
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        let path = self
+            .current_file
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(self.default_file_name()));
+
+        let result = self.save_theme_file(&path);
+        self.file_error = result
+            .err()
+            .map(|error| format!("Failed to save {}: {error}", path.display()));
+    }
+
+    /// Asks for a location and saves the theme there.
+    pub fn save_as_dialog(&mut self) {
+        let file_name = self.default_file_name();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = theme_file_dialog().set_file_name(file_name).save_file() {
+            let result = self.save_theme_file(&path);
+            self.file_error = result
+                .err()
+                .map(|error| format!("Failed to save {}: {error}", path.display()));
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let config = self.current_config.clone();
+            let json = match serde_json::to_string_pretty(&config) {
+                Ok(json) => json,
+                Err(error) => {
+                    self.file_error = Some(format!("Failed to save {file_name}: {error}"));
+                    return;
+                }
+            };
+            let dialog = theme_file_dialog().set_file_name(file_name);
+            self.spawn_file_dialog(async move {
+                let file = dialog.save_file().await?;
+                Some(match file.write(json.as_bytes()).await {
+                    Ok(()) => FileDialogResult::Saved {
+                        name: file.file_name(),
+                        config: Box::new(config),
+                    },
+                    Err(error) => FileDialogResult::Failed(format!("Failed to save: {error}")),
+                })
+            });
+        }
+    }
+
+    /// The name of the current file, or one derived from the theme name.
+    fn default_file_name(&self) -> String {
+        self.current_file
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| {
+                format!(
+                    "{}{}",
+                    crate::ThemeRegistry::slugify(&self.current_config.name),
+                    crate::ThemeRegistry::THEME_FILE_EXTENSION
+                )
+            })
+    }
+
+    /// Whether a browser file dialog is open. Always `false` on native.
+    pub fn is_file_dialog_open(&self) -> bool {
+        #[cfg(target_arch = "wasm32")]
+        return self.file_dialog.is_some();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        false
+    }
+
+    /// Loads theme files dropped onto the app according to [`Self::file_drop`].
+    ///
+    /// Returns an error message for each file that is not a valid theme.
+    pub(crate) fn handle_dropped_files(&mut self, files: &[egui::DroppedFile]) -> Vec<String> {
+        let Some(action) = self.file_drop else {
+            return Vec::new();
+        };
+        let mut errors = Vec::new();
+        let mut opened = None;
+        for file in files {
+            let (path, config) = match read_dropped_file(file) {
+                Ok(theme) => theme,
+                Err(error) => {
+                    errors.push(error);
+                    continue;
+                }
+            };
+            match action {
+                ThemeFileDrop::Apply => {
+                    opened = Some(FileAction::OpenDropped {
+                        config: Box::new(config),
+                        path,
+                    });
+                }
+                ThemeFileDrop::AddToRegistry => {
+                    let id = self.registry.unique_id(&config.name);
+                    let file = file.path.clone();
+                    let _ = self.registry.register_with_id(
+                        id.clone(),
+                        config,
+                        crate::ThemeSource::User,
+                        file,
+                    );
+                    self.persist_user_presets();
+                    self.emit(ThemeEditorEvent::PresetAdded { id });
+                }
+            }
+        }
+        // Of several dropped themes, the last one ends up open.
+        if let Some(action) = opened {
+            self.request_file_action(action);
+        }
+        errors
+    }
+
+    /// Runs `action` now, or asks first if it would discard unsaved changes.
+    pub(crate) fn request_file_action(&mut self, action: FileAction) {
+        if self.has_unsaved_changes() {
+            self.discard_prompt = Some(action);
+        } else {
+            self.run_file_action(action);
+        }
+    }
+
+    pub(crate) fn run_file_action(&mut self, action: FileAction) {
+        self.discard_prompt = None;
+        match action {
+            FileAction::Open => self.open_file_dialog(),
+            FileAction::OpenRecent(path) => {
+                let result = self.load_theme_file(&path);
+                self.file_error = result
+                    .err()
+                    .map(|error| format!("Failed to open {}: {error}", path.display()));
+            }
+            FileAction::OpenDropped { config, path } => {
+                // Keep a copy so the file can be reopened from the recent list.
+                #[cfg(target_arch = "wasm32")]
+                let _ = config.save_to_file(&path);
+                self.finish_open(*config, path);
+            }
+        }
+    }
+
+    /// Picks up the result of a browser file dialog once it is available.
+    pub(crate) fn poll_file_dialog(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            use std::sync::mpsc::TryRecvError;
+
+            let Some(task) = &self.file_dialog else {
+                return;
+            };
+            let result = match task.receiver.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    // The dialog was cancelled.
+                    self.file_dialog = None;
+                    return;
+                }
+            };
+            self.file_dialog = None;
+            match result {
+                FileDialogResult::Opened { name, contents } => {
+                    let path = PathBuf::from(&name);
+                    let config = serde_json::from_slice::<ThemeConfig>(&contents)
+                        .map_err(|error| error.to_string())
+                        .and_then(|config| {
+                            config.validate().map_err(|error| error.to_string())?;
+                            Ok(config)
+                        });
+                    match config {
+                        Ok(config) => {
+                            // Keep a copy so the file can be reopened from the recent list.
+                            let _ = config.save_to_file(&path);
+                            self.finish_open(config, path);
+                        }
+                        Err(error) => {
+                            self.file_error = Some(format!("Failed to open {name}: {error}"));
+                        }
+                    }
+                }
+                FileDialogResult::Saved { name, config } => {
+                    let path = PathBuf::from(name);
+                    let _ = config.save_to_file(&path);
+                    self.finish_save(path, *config);
+                }
+                FileDialogResult::Failed(error) => self.file_error = Some(error),
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn spawn_file_dialog(
+        &mut self,
+        dialog: impl std::future::Future<Output = Option<FileDialogResult>> + 'static,
+    ) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Some(result) = dialog.await {
+                let _ = sender.send(result);
+            }
+        });
+        self.file_dialog = Some(FileDialogTask { receiver });
+    }
+
+    fn finish_open(&mut self, config: ThemeConfig, path: PathBuf) {
+        self.current_config = config;
+        self.selected_theme_id = None;
+        self.saved_config = self.current_config.clone();
+        self.record_change(format!("Open '{}'", self.current_config.name));
+        self.remember_file(path.clone());
+        self.file_error = None;
+        self.emit(ThemeEditorEvent::LoadedFromFile { path });
+    }
+
+    fn finish_save(&mut self, path: PathBuf, config: ThemeConfig) {
+        self.saved_config = config;
+        self.remember_file(path.clone());
+        self.file_error = None;
+        self.emit(ThemeEditorEvent::SavedToFile { path });
+    }
+
+    fn remember_file(&mut self, path: PathBuf) {
+        self.recent_files.retain(|recent| *recent != path);
+        self.recent_files.insert(0, path.clone());
+        self.recent_files.truncate(self.max_recent_files);
+        self.current_file = Some(path);
+    }
+}
+
+/// Reads a dropped file: from disk on native, from the dropped bytes on the web.
+fn read_dropped_file(file: &egui::DroppedFile) -> Result<(PathBuf, ThemeConfig), String> {
+    let path = file
+        .path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(&file.name));
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| file.name.clone());
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    if !is_json {
+        return Err(format!("{name} is not a theme file"));
+    }
+
+    let config = match (&file.bytes, &file.path) {
+        (Some(bytes), _) => serde_json::from_slice(bytes).map_err(|error| error.to_string()),
+        (None, Some(path)) => ThemeConfig::load_from_file(path).map_err(|error| error.to_string()),
+        (None, None) => Err("the file has no contents".to_string()),
+    }
+    .and_then(|config: ThemeConfig| {
+        config.validate().map_err(|error| error.to_string())?;
+        Ok(config)
+    });
+    config
+        .map(|config| (path, config))
+        .map_err(|error| format!("Failed to load {name}: {error}"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn theme_file_dialog() -> rfd::FileDialog {
+    rfd::FileDialog::new().add_filter("Theme", &["json"])
+}
+
+#[cfg(target_arch = "wasm32")]
+fn theme_file_dialog() -> rfd::AsyncFileDialog {
+    rfd::AsyncFileDialog::new().add_filter("Theme", &["json"])
+}
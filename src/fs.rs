@@ -0,0 +1,63 @@
+//! Reads and writes theme files: the file system on native, and the browser's
+//! `localStorage` on the web, where each path is a key prefixed with
+//! [`crate::LocalStorage::FILE_KEY_PREFIX`].
+
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read_to_string(path: &Path) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn write(path: &Path, contents: &str) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
+
+/// The files directly inside `directory`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read_dir(directory: &Path) -> std::io::Result<Vec<PathBuf>> {
+    Ok(std::fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn read_to_string(path: &Path) -> std::io::Result<String> {
+    crate::LocalStorage::open()?
+        .get(&file_key(path))?
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} is not in local storage", path.display()),
+            )
+        })
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn write(path: &Path, contents: &str) -> std::io::Result<()> {
+    crate::LocalStorage::open()?.set(&file_key(path), contents)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn read_dir(directory: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let paths = crate::LocalStorage::open()?
+        .keys()?
+        .into_iter()
+        .filter_map(|key| {
+            let path = PathBuf::from(key.strip_prefix(crate::LocalStorage::FILE_KEY_PREFIX)?);
+            (path.parent() == Some(directory)).then_some(path)
+        })
+        .collect();
+    Ok(paths)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn file_key(path: &Path) -> String {
+    format!(
+        "{}{}",
+        crate::LocalStorage::FILE_KEY_PREFIX,
+        path.to_string_lossy().replace('\\', "/")
+    )
+}
@@ -0,0 +1,238 @@
+use crate::config::ThemeConfig;
+
+/// The editor state captured by a history step.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct HistorySnapshot {
+    pub config: ThemeConfig,
+    pub selected_theme_id: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct HistoryEntry {
+    /// Describes the change that was made *after* this snapshot.
+    pub label: String,
+    pub snapshot: HistorySnapshot,
+}
+
+/// Undo/redo stacks of [`HistorySnapshot`]s.
+///
+/// Discrete actions such as preset switches are recorded with [`Self::record`].
+/// Everything else is picked up by [`Self::track`], which compares against the
+/// last known snapshot and merges continuous edits (a slider drag, a color
+/// picker drag, typing into a field) into a single step.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ThemeHistory {
+    undo_stack: Vec<HistoryEntry>,
+    redo_stack: Vec<HistoryEntry>,
+    baseline: HistorySnapshot,
+    #[serde(skip)]
+    coalescing: bool,
+    pub max_entries: usize,
+}
+
+impl ThemeHistory {
+    pub const DEFAULT_MAX_ENTRIES: usize = 100;
+
+    pub fn new(initial: HistorySnapshot) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            baseline: initial,
+            coalescing: false,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+        }
+    }
+
+    /// Records a discrete change whose result is `current`.
+    pub fn record(&mut self, label: impl Into<String>, current: HistorySnapshot) {
+        if current == self.baseline {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.baseline, current);
+        self.push_undo(label.into(), previous);
+        self.coalescing = false;
+    }
+
+    /// Records any change since the last call.
+    ///
+    /// While `continuous` stays `true` consecutive changes are merged into the
+    /// step that started them.
+    pub fn track(
+        &mut self,
+        config: &ThemeConfig,
+        selected_theme_id: Option<&str>,
+        continuous: bool,
+    ) {
+        let unchanged = self.baseline.config == *config
+            && self.baseline.selected_theme_id.as_deref() == selected_theme_id;
+        if unchanged {
+            if !continuous {
+                self.coalescing = false;
+            }
+            return;
+        }
+
+        let current = HistorySnapshot {
+            config: config.clone(),
+            selected_theme_id: selected_theme_id.map(str::to_string),
+        };
+        let previous = std::mem::replace(&mut self.baseline, current);
+        if !(continuous && self.coalescing) {
+            let label = describe_change(&previous.config, config);
+            self.push_undo(label, previous);
+        }
+        self.coalescing = continuous;
+    }
+
+    /// Steps back, returning the snapshot to restore.
+    pub fn undo(&mut self) -> Option<HistorySnapshot> {
+        let entry = self.undo_stack.pop()?;
+        let current = std::mem::replace(&mut self.baseline, entry.snapshot.clone());
+        self.redo_stack.push(HistoryEntry {
+            label: entry.label,
+            snapshot: current,
+        });
+        self.coalescing = false;
+        Some(entry.snapshot)
+    }
+
+    /// Steps forward again, returning the snapshot to restore.
+    pub fn redo(&mut self) -> Option<HistorySnapshot> {
+        let entry = self.redo_stack.pop()?;
+        let current = std::mem::replace(&mut self.baseline, entry.snapshot.clone());
+        self.undo_stack.push(HistoryEntry {
+            label: entry.label,
+            snapshot: current,
+        });
+        self.coalescing = false;
+        Some(entry.snapshot)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Steps that can be undone, oldest first.
+    pub fn undo_entries(&self) -> &[HistoryEntry] {
+        &self.undo_stack
+    }
+
+    /// Steps that can be redone, most recently undone last.
+    pub fn redo_entries(&self) -> &[HistoryEntry] {
+        &self.redo_stack
+    }
+
+    pub fn clear(&mut self, current: HistorySnapshot) {
+        *self = Self {
+            max_entries: self.max_entries,
+            ..Self::new(current)
+        };
+    }
+
+    fn push_undo(&mut self, label: String, snapshot: HistorySnapshot) {
+        self.undo_stack.push(HistoryEntry { label, snapshot });
+        if self.undo_stack.len() > self.max_entries {
+            let excess = self.undo_stack.len() - self.max_entries;
+            self.undo_stack.drain(..excess);
+        }
+        self.redo_stack.clear();
+    }
+}
+
+fn describe_change(from: &ThemeConfig, to: &ThemeConfig) -> String {
+    let diff = from.diff(to);
+    match diff.properties.as_slice() {
+        [] => "Edit theme".to_string(),
+        [property] => format!("Change {}", property.key),
+        properties => format!("Change {} properties", properties.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_panel_fill(value: u8) -> ThemeConfig {
+        ThemeConfig {
+            override_panel_fill: Some([value, value, value, 255]),
+            ..Default::default()
+        }
+    }
+
+    fn history() -> ThemeHistory {
+        ThemeHistory::new(HistorySnapshot {
+            config: ThemeConfig::default(),
+            selected_theme_id: None,
+        })
+    }
+
+    #[test]
+    fn a_drag_is_one_step() {
+        let mut history = history();
+        for value in 1..=10 {
+            history.track(&with_panel_fill(value), None, true);
+        }
+        // The drag ends with the pointer released on the last value.
+        history.track(&with_panel_fill(10), None, false);
+
+        assert_eq!(history.undo_entries().len(), 1);
+        assert_eq!(
+            history.undo_entries()[0].label,
+            "Change override_panel_fill"
+        );
+        let restored = history.undo().unwrap();
+        assert_eq!(restored.config, ThemeConfig::default());
+    }
+
+    #[test]
+    fn discrete_edits_are_separate_steps() {
+        let mut history = history();
+        for value in 1..=3 {
+            history.track(&with_panel_fill(value), None, false);
+        }
+        assert_eq!(history.undo_entries().len(), 3);
+
+        assert_eq!(history.undo().unwrap().config, with_panel_fill(2));
+        assert_eq!(history.undo().unwrap().config, with_panel_fill(1));
+        assert_eq!(history.redo().unwrap().config, with_panel_fill(2));
+    }
+
+    #[test]
+    fn drags_separated_by_a_release_are_separate_steps() {
+        let mut history = history();
+        history.track(&with_panel_fill(1), None, true);
+        history.track(&with_panel_fill(2), None, true);
+        history.track(&with_panel_fill(2), None, false);
+        history.track(&with_panel_fill(3), None, true);
+        history.track(&with_panel_fill(4), None, true);
+
+        assert_eq!(history.undo_entries().len(), 2);
+        assert_eq!(history.undo().unwrap().config, with_panel_fill(2));
+    }
+
+    #[test]
+    fn a_recorded_step_ends_a_drag() {
+        let mut history = history();
+        history.track(&with_panel_fill(1), None, true);
+        history.record(
+            "Select preset",
+            HistorySnapshot {
+                config: ThemeConfig::nord_preset(),
+                selected_theme_id: Some("nord".to_string()),
+            },
+        );
+        history.track(&with_panel_fill(2), None, true);
+
+        let labels = history
+            .undo_entries()
+            .iter()
+            .map(|entry| entry.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels[..2], ["Change override_panel_fill", "Select preset"]);
+    }
+}
@@ -7,9 +7,28 @@
 //!
 //! - **Full Theme Configuration**: Customize all visual aspects of your egui application
 //! - **Built-in Presets**: Dark and Light themes included out of the box
+//! - **Theme Registry**: Look up built-in, app and user themes by stable id and discover theme files from directories
+//! - **Theme Gallery**: Browse themes as painted miniature cards grouped by dark and light and filtered by tag
+//! - **Hover to Preview**: Try a preset on the whole app by hovering it in a picker, without touching undo history
+//! - **User Presets**: Create, duplicate, rename, reorder and delete presets next to the read-only built-ins, saved to a file of your choice
 //! - **Random Theme Generation**: Generate completely random themes with a single click
-//! - **Live Preview**: See changes in real-time as you edit
-//! - **Persistence**: Save and load themes to/from JSON files
+//! - **Undo/Redo**: Every edit, preset switch and randomize can be undone with Ctrl+Z / Ctrl+Shift+Z
+//! - **Diff & Patch**: Compare two themes property by property and apply the differences to another theme
+//! - **Theme Overlays**: Layer partial themes over the user's theme at runtime, e.g. a production warning tint
+//! - **Scoped Themes**: Render a panel, window or single widget with a different theme than the rest of the app
+//! - **Semantic Colors**: Success, info, accent and muted roles alongside warning and error, filled in by every preset
+//! - **Semantic Variants**: Primary, secondary, danger, success and ghost buttons, toggles and frames that follow the theme
+//! - **Context Access**: Read the active theme, its semantic colors and custom properties from any `Context` or `Ui`
+//! - **Property Registry**: Iterate, read and write every themable property by key, e.g. `widgets.hovered.bg_fill`
+//! - **Change Notifications**: The editor returns which properties changed and what the user did, or runs your callbacks
+//! - **Palette Overview**: See every distinct color with its usage count and recolor all of its properties at once
+//! - **Simple Mode**: Theme an app from 8 core colors and 3 sliders, then switch to the full editor without losing edits
+//! - **Live Preview**: See changes in real-time as you edit, or in a preview window with Apply/Revert and an optional auto-revert countdown
+//! - **Persistence**: Save and load themes to/from JSON files, backed by `localStorage` on the web
+//! - **Drag and Drop**: Opt in to applying theme files dropped onto the app, or adding them to the presets
+//! - **Remembered Themes**: Restore the user's chosen theme through egui memory or app storage
+//! - **Session Restore**: Serialize the whole editor session, from undo history to open sections, and resume it later
+//! - **Hot Reload**: Watch a theme file and apply edits made in a text editor while the app runs
 //! - **Interactive Theme Editor**: Full-featured UI with color pickers and preview panel
 //!
 //! ## Quick Start
@@ -58,9 +77,61 @@
 //! ```
 
 mod config;
+mod context;
+mod diff;
+mod files;
+mod fs;
+mod history;
+mod palette;
+mod persistence;
+mod presets;
+mod properties;
+mod registry;
+mod response;
+mod scope;
+mod semantic;
+mod session;
+mod simple;
+mod stack;
 mod state;
 mod ui;
+mod value;
+mod variants;
+#[cfg(not(target_arch = "wasm32"))]
+mod watch;
+#[cfg(target_arch = "wasm32")]
+mod web_storage;
 
-pub use config::ThemeConfig;
+pub use config::{ThemeConfig, ThemeValidationError};
+pub use context::{ActiveTheme, ThemeContextExt};
+pub use diff::{PatchError, PropertyChange, PropertyDiff, ThemeDiff, ThemePatch};
+pub use files::ThemeFileDrop;
+pub use history::{HistoryEntry, HistorySnapshot, ThemeHistory};
+pub use palette::PaletteColor;
+pub use persistence::{PersistedTheme, ThemeStorage, THEME_STORAGE_KEY};
+pub use properties::{
+    find_property, PropertyCategory, PropertyError, PropertyKind, ThemeProperty, THEME_PROPERTIES,
+};
+pub use registry::{
+    DiscoveryReport, RegistryError, StoredTheme, ThemeEntry, ThemeRegistry, ThemeSource,
+};
+pub use response::{ThemeEditorEvent, ThemeEditorHooks, ThemeEditorResponse};
+pub use scope::{themed_frame, themed_window_frame, with_theme};
+pub use semantic::SemanticColors;
+pub use session::{ThemeEditorSession, SESSION_STORAGE_KEY};
+pub use simple::{CoreColor, GlobalControl};
+pub use stack::{ThemeLayer, ThemeStack};
 pub use state::ThemeEditorState;
-pub use ui::{render_theme_editor, render_theme_panel};
+pub use ui::{
+    render_apply_confirmation, render_file_drop, render_theme_editor, render_theme_gallery,
+    render_theme_panel, render_theme_preview, render_theme_switcher,
+};
+pub use value::PropertyValue;
+pub use variants::{
+    ResolvedVariant, ResolvedVariantState, ThemeVariants, VariantStateStyle, VariantStyle,
+    VariantUiExt, WidgetVariant,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use watch::{ThemeFileWatcher, ThemeReloadEvent};
+#[cfg(target_arch = "wasm32")]
+pub use web_storage::LocalStorage;
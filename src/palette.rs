@@ -0,0 +1,103 @@
+use crate::config::ThemeConfig;
+use crate::properties::{find_property, PropertyKind, THEME_PROPERTIES};
+use crate::value::PropertyValue;
+use egui::{Color32, Visuals};
+
+/// A distinct color used by a theme and the color properties that resolve to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteColor {
+    pub color: Color32,
+    /// Keys of the properties using this color, in registry order.
+    pub properties: Vec<&'static str>,
+}
+
+impl ThemeConfig {
+    /// Every distinct color in effect, given visuals already computed from this theme.
+    ///
+    /// Colors are listed in the order their first property appears in
+    /// [`THEME_PROPERTIES`], so the order stays put while a color is edited.
+    pub fn palette(&self, visuals: &Visuals) -> Vec<PaletteColor> {
+        self.palette_without(visuals, &[])
+    }
+
+    /// The palette while the properties of `held` are edited together.
+    ///
+    /// They stay one swatch at `index` even when their color passes through
+    /// another swatch's color, so the groups don't merge and the swatches
+    /// after them don't shift.
+    pub(crate) fn palette_holding(
+        &self,
+        visuals: &Visuals,
+        index: usize,
+        held: &[&'static str],
+    ) -> Vec<PaletteColor> {
+        let mut palette = self.palette_without(visuals, held);
+        let color = held
+            .iter()
+            .find_map(|key| find_property(key)?.resolve(self, visuals).as_color32());
+        if let Some(color) = color {
+            palette.insert(
+                index.min(palette.len()),
+                PaletteColor {
+                    color,
+                    properties: held.to_vec(),
+                },
+            );
+        }
+        palette
+    }
+
+    fn palette_without(&self, visuals: &Visuals, excluded: &[&str]) -> Vec<PaletteColor> {
+        let mut palette: Vec<PaletteColor> = Vec::new();
+        for property in THEME_PROPERTIES.iter().filter(|property| {
+            property.kind == PropertyKind::Color && !excluded.contains(&property.key)
+        }) {
+            let Some(color) = property.resolve(self, visuals).as_color32() else {
+                continue;
+            };
+            match palette.iter_mut().find(|entry| entry.color == color) {
+                Some(entry) => entry.properties.push(property.key),
+                None => palette.push(PaletteColor {
+                    color,
+                    properties: vec![property.key],
+                }),
+            }
+        }
+        palette
+    }
+
+    /// Overrides every property of `color` with `value`.
+    pub fn set_palette_color(&mut self, color: &PaletteColor, value: Color32) {
+        for key in &color.properties {
+            let _ = self.set_property(key, Some(PropertyValue::from(value)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_swatch_does_not_merge_on_collision() {
+        let first = Color32::from_rgb(1, 2, 3);
+        let second = Color32::from_rgb(4, 5, 6);
+        let mut config = ThemeConfig::default();
+        let _ = config.set_property("hyperlink_color", Some(first.into()));
+        let _ = config.set_property("selection.bg_fill", Some(second.into()));
+        let palette = config.palette(&config.to_visuals());
+        let index = |color| palette.iter().position(|entry| entry.color == color);
+        let (edited, other) = (index(first).unwrap(), index(second).unwrap());
+
+        // Drag the first swatch's color through the second one's.
+        config.set_palette_color(&palette[edited], second);
+        let visuals = config.to_visuals();
+        assert_eq!(config.palette(&visuals).len(), palette.len() - 1);
+
+        let held = config.palette_holding(&visuals, edited, &palette[edited].properties);
+        assert_eq!(held.len(), palette.len());
+        assert_eq!(held[edited].properties, palette[edited].properties);
+        assert_eq!(held[edited].color, second);
+        assert_eq!(held[other], palette[other]);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::config::ThemeConfig;
+use crate::state::ThemeEditorState;
+use std::collections::{BTreeMap, HashMap};
+
+/// The key used by the `*_storage` and `*_memory` helpers on [`ThemeEditorState`].
+pub const THEME_STORAGE_KEY: &str = "egui_thematic_active_theme";
+
+/// A string key/value store, shaped like `eframe::Storage`.
+///
+/// Implement this for your app's storage to persist the active theme. For
+/// eframe the implementation is a direct forward:
+///
+/// ```rust,ignore
+/// struct EframeStorage<'a>(&'a mut dyn eframe::Storage);
+///
+/// impl ThemeStorage for EframeStorage<'_> {
+///     fn get_string(&self, key: &str) -> Option<String> {
+///         self.0.get_string(key)
+///     }
+///
+///     fn set_string(&mut self, key: &str, value: String) {
+///         self.0.set_string(key, value);
+///     }
+/// }
+/// ```
+pub trait ThemeStorage {
+    fn get_string(&self, key: &str) -> Option<String>;
+    fn set_string(&mut self, key: &str, value: String);
+}
+
+impl ThemeStorage for HashMap<String, String> {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.insert(key.to_string(), value);
+    }
+}
+
+impl ThemeStorage for BTreeMap<String, String> {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.insert(key.to_string(), value);
+    }
+}
+
+/// The user's chosen theme in a form suitable for persisting.
+///
+/// When the active theme is an unmodified registry theme only its id is kept,
+/// so later changes to that theme (for example an updated app preset) are
+/// picked up on restore. Local edits are stored as the full config.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct PersistedTheme {
+    pub registry_id: Option<String>,
+    pub config: Option<ThemeConfig>,
+}
+
+impl PersistedTheme {
+    pub fn from_editor(editor_state: &ThemeEditorState) -> Self {
+        let registry_entry = editor_state
+            .selected_theme_id
+            .as_deref()
+            .and_then(|id| editor_state.registry.get(id));
+        let config = match registry_entry {
+            Some(entry) if entry.config == *editor_state.committed_config() => None,
+            _ => Some(editor_state.committed_config().clone()),
+        };
+        Self {
+            registry_id: registry_entry.map(|entry| entry.id.clone()),
+            config,
+        }
+    }
+
+    /// Restores this theme into the editor, returning `false` if nothing could be restored.
+    pub fn apply_to_editor(self, editor_state: &mut ThemeEditorState) -> bool {
+        let selected = self
+            .registry_id
+            .as_deref()
+            .is_some_and(|id| editor_state.select_theme(id));
+        let restored = match self.config {
+            Some(config) => {
+                if !selected {
+                    editor_state.selected_theme_id = None;
+                }
+                editor_state.current_config = config;
+                true
+            }
+            None => selected,
+        };
+        if restored {
+            editor_state.commit_current();
+            editor_state.clear_history();
+        }
+        restored
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl ThemeEditorState {
+    /// Saves the active theme to `storage`. Call this when the app shuts down.
+    pub fn save_to_storage(&self, storage: &mut dyn ThemeStorage) {
+        if let Ok(json) = PersistedTheme::from_editor(self).to_json() {
+            storage.set_string(THEME_STORAGE_KEY, json);
+        }
+    }
+
+    /// Restores the theme saved by [`Self::save_to_storage`]. Call this at startup.
+    ///
+    /// Returns `false` if nothing was stored or the stored theme could not be read.
+    pub fn load_from_storage(&mut self, storage: &dyn ThemeStorage) -> bool {
+        storage
+            .get_string(THEME_STORAGE_KEY)
+            .and_then(|json| PersistedTheme::from_json(&json).ok())
+            .is_some_and(|persisted| persisted.apply_to_editor(self))
+    }
+
+    /// Saves the active theme into egui's persisted memory.
+    ///
+    /// The theme survives restarts when the integration persists egui memory,
+    /// as eframe does with its `persistence` feature.
+    pub fn save_to_memory(&self, ctx: &egui::Context) {
+        if let Ok(json) = PersistedTheme::from_editor(self).to_json() {
+            ctx.data_mut(|data| data.insert_persisted(egui::Id::new(THEME_STORAGE_KEY), json));
+        }
+    }
+
+    /// Restores the theme saved by [`Self::save_to_memory`].
+    pub fn load_from_memory(&mut self, ctx: &egui::Context) -> bool {
+        ctx.data_mut(|data| data.get_persisted::<String>(egui::Id::new(THEME_STORAGE_KEY)))
+            .and_then(|json| PersistedTheme::from_json(&json).ok())
+            .is_some_and(|persisted| persisted.apply_to_editor(self))
+    }
+}
@@ -0,0 +1,153 @@
+use crate::registry::ThemeSource;
+use crate::state::ThemeEditorState;
+use std::path::PathBuf;
+
+impl ThemeEditorState {
+    /// Persists user presets to `path` and registers the ones already saved there.
+    ///
+    /// A missing file is not an error; it is created when the first preset is saved.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use egui_thematic::ThemeEditorState;
+    ///
+    /// let editor_state = ThemeEditorState::default().with_user_presets("user_presets.json");
+    /// ```
+    pub fn with_user_presets(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        if let Err(error) = self.registry.load_user_themes(&path) {
+            let missing = error
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|error| error.kind() == std::io::ErrorKind::NotFound);
+            if !missing {
+                self.file_error = Some(format!(
+                    "Failed to load user presets from {}: {error}",
+                    path.display()
+                ));
+            }
+        }
+        self.user_presets_path = Some(path);
+        self
+    }
+
+    pub fn is_user_preset(&self, id: &str) -> bool {
+        self.registry
+            .get(id)
+            .is_some_and(|entry| entry.source == ThemeSource::User)
+    }
+
+    /// Saves the current theme as a new user preset named `name` and selects it.
+    pub fn create_user_preset(&mut self, name: &str) -> String {
+        let id = self.registry.unique_id(name);
+        self.current_config.name = name.to_string();
+        let _ = self.registry.register_with_id(
+            id.clone(),
+            self.current_config.clone(),
+            ThemeSource::User,
+            None,
+        );
+        self.selected_theme_id = Some(id.clone());
+        self.record_change(format!("Create preset '{name}'"));
+        self.persist_user_presets();
+        id
+    }
+
+    /// Copies any registered theme, built-in ones included, into a new user preset.
+    pub fn duplicate_preset(&mut self, id: &str) -> Option<String> {
+        let mut config = self.registry.get(id)?.config.clone();
+        config.name = format!("{} Copy", config.name);
+        let copy = self.registry.unique_id(&config.name);
+        let _ = self
+            .registry
+            .register_with_id(copy.clone(), config, ThemeSource::User, None);
+        self.persist_user_presets();
+        Some(copy)
+    }
+
+    /// Overwrites a user preset with the current theme.
+    pub fn update_user_preset(&mut self, id: &str) -> bool {
+        if !self.is_user_preset(id) {
+            return false;
+        }
+        let mut config = self.current_config.clone();
+        if let Some(entry) = self.registry.get_mut(id) {
+            config.name = entry.config.name.clone();
+            entry.config = config;
+        }
+        self.persist_user_presets();
+        true
+    }
+
+    /// Renames a user preset, keeping its id. Built-in and app themes are read-only.
+    pub fn rename_user_preset(&mut self, id: &str, name: &str) -> bool {
+        if !self.is_user_preset(id) {
+            return false;
+        }
+        if let Some(entry) = self.registry.get_mut(id) {
+            entry.config.name = name.to_string();
+        }
+        if self.selected_theme_id.as_deref() == Some(id) {
+            self.current_config.name = name.to_string();
+        }
+        self.persist_user_presets();
+        true
+    }
+
+    pub fn delete_user_preset(&mut self, id: &str) -> bool {
+        if !self.is_user_preset(id) {
+            return false;
+        }
+        let Some(entry) = self.registry.remove(id) else {
+            return false;
+        };
+        if self.selected_theme_id.as_deref() == Some(id) {
+            self.selected_theme_id = None;
+        }
+        self.record_change(format!("Delete preset '{}'", entry.config.name));
+        self.persist_user_presets();
+        true
+    }
+
+    /// Moves a user preset up (negative `offset`) or down the preset list.
+    pub fn move_user_preset(&mut self, id: &str, offset: isize) -> bool {
+        if !self.is_user_preset(id) || !self.registry.move_entry(id, offset) {
+            return false;
+        }
+        self.persist_user_presets();
+        true
+    }
+
+    pub(crate) fn persist_user_presets(&mut self) {
+        let Some(path) = &self.user_presets_path else {
+            return;
+        };
+        if let Err(error) = self.registry.save_user_themes(path) {
+            self.file_error = Some(format!(
+                "Failed to save user presets to {}: {error}",
+                path.display()
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_after_delete_does_not_select_the_deleted_preset() {
+        let mut editor_state = ThemeEditorState::default();
+        let id = editor_state.create_user_preset("Mine");
+        assert!(editor_state.delete_user_preset(&id));
+        let label = editor_state
+            .history
+            .undo_entries()
+            .last()
+            .map(|entry| &entry.label);
+        assert_eq!(label.map(String::as_str), Some("Delete preset 'Mine'"));
+
+        assert!(editor_state.undo());
+        assert_eq!(editor_state.selected_theme_id, None);
+    }
+}
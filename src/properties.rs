@@ -0,0 +1,631 @@
+use crate::config::ThemeConfig;
+use crate::value::PropertyValue;
+use egui::Visuals;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PropertyKind {
+    Color,
+    Float,
+    /// A whole number stored as `u8`, such as a corner radius or shadow size.
+    Integer,
+    Bool,
+}
+
+impl PropertyKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PropertyKind::Color => "color",
+            PropertyKind::Float => "number",
+            PropertyKind::Integer => "integer",
+            PropertyKind::Bool => "boolean",
+        }
+    }
+}
+
+/// Groups properties the way the theme editor shows them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PropertyCategory {
+    Text,
+    NoninteractiveWidgets,
+    InactiveWidgets,
+    HoveredWidgets,
+    ActiveWidgets,
+    OpenWidgets,
+    Selection,
+    WindowsAndPanels,
+    SpecialColors,
+    UiOptions,
+}
+
+impl PropertyCategory {
+    pub const ALL: [PropertyCategory; 10] = [
+        PropertyCategory::Text,
+        PropertyCategory::NoninteractiveWidgets,
+        PropertyCategory::InactiveWidgets,
+        PropertyCategory::HoveredWidgets,
+        PropertyCategory::ActiveWidgets,
+        PropertyCategory::OpenWidgets,
+        PropertyCategory::Selection,
+        PropertyCategory::WindowsAndPanels,
+        PropertyCategory::SpecialColors,
+        PropertyCategory::UiOptions,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PropertyCategory::Text => "Text",
+            PropertyCategory::NoninteractiveWidgets => "Noninteractive",
+            PropertyCategory::InactiveWidgets => "Inactive (Resting)",
+            PropertyCategory::HoveredWidgets => "Hovered",
+            PropertyCategory::ActiveWidgets => "Active (Clicking)",
+            PropertyCategory::OpenWidgets => "Open",
+            PropertyCategory::Selection => "Selection",
+            PropertyCategory::WindowsAndPanels => "Windows & Panels",
+            PropertyCategory::SpecialColors => "Special Colors",
+            PropertyCategory::UiOptions => "UI Options",
+        }
+    }
+
+    pub fn properties(self) -> impl Iterator<Item = &'static ThemeProperty> {
+        THEME_PROPERTIES
+            .iter()
+            .filter(move |property| property.category == self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyError {
+    UnknownProperty(String),
+    WrongKind {
+        key: &'static str,
+        expected: PropertyKind,
+        found: PropertyValue,
+    },
+}
+
+impl std::fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyError::UnknownProperty(key) => write!(f, "unknown theme property '{key}'"),
+            PropertyError::WrongKind {
+                key,
+                expected,
+                found,
+            } => write!(f, "'{key}' expects a {} but got {found}", expected.label()),
+        }
+    }
+}
+
+impl std::error::Error for PropertyError {}
+
+/// Describes one themable property of a [`ThemeConfig`].
+///
+/// Every `override_*` field has an entry in [`THEME_PROPERTIES`]. Tools can
+/// iterate the registry to build UIs or read and write properties by key
+/// without matching on field names.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_thematic::{find_property, PropertyValue, ThemeConfig};
+///
+/// let mut theme = ThemeConfig::dark_preset();
+/// let property = find_property("widgets.hovered.bg_fill").unwrap();
+/// property
+///     .set(&mut theme, Some(PropertyValue::Color([200, 80, 40, 255])))
+///     .unwrap();
+///
+/// assert_eq!(theme.override_widget_hovered_bg_fill, Some([200, 80, 40, 255]));
+/// assert_eq!(property.category.label(), "Hovered");
+/// ```
+#[derive(Clone, Copy)]
+pub struct ThemeProperty {
+    /// Dotted path mirroring [`egui::Visuals`], e.g. `widgets.hovered.bg_fill`.
+    pub key: &'static str,
+    /// The [`ThemeConfig`] field that stores the override, as used in theme files and diffs.
+    pub field: &'static str,
+    pub name: &'static str,
+    pub category: PropertyCategory,
+    pub kind: PropertyKind,
+    /// Sensible `(min, max)` editing range for numeric properties.
+    pub range: Option<(f32, f32)>,
+    /// Fine-tuning controls the editor tucks away behind an "Advanced" header.
+    pub advanced: bool,
+    get: fn(&ThemeConfig) -> Option<PropertyValue>,
+    set: fn(&mut ThemeConfig, Option<&PropertyValue>) -> bool,
+    resolve: fn(&ThemeConfig, &Visuals) -> PropertyValue,
+}
+
+impl std::fmt::Debug for ThemeProperty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThemeProperty")
+            .field("key", &self.key)
+            .field("field", &self.field)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ThemeProperty {
+    /// The override stored in `config`, or `None` if the property uses its default.
+    pub fn get(&self, config: &ThemeConfig) -> Option<PropertyValue> {
+        (self.get)(config)
+    }
+
+    /// Sets the override in `config`. `None` removes it.
+    pub fn set(
+        &self,
+        config: &mut ThemeConfig,
+        value: Option<PropertyValue>,
+    ) -> Result<(), PropertyError> {
+        if (self.set)(config, value.as_ref()) {
+            return Ok(());
+        }
+        Err(PropertyError::WrongKind {
+            key: self.key,
+            expected: self.kind,
+            found: value.expect("clearing an override always succeeds"),
+        })
+    }
+
+    pub fn is_overridden(&self, config: &ThemeConfig) -> bool {
+        self.get(config).is_some()
+    }
+
+    /// The value in effect for `config`, given visuals already computed from it.
+    pub fn resolve(&self, config: &ThemeConfig, visuals: &Visuals) -> PropertyValue {
+        (self.resolve)(config, visuals)
+    }
+
+    /// The value used when a dark or light theme does not override the property.
+    pub fn default_value(&self, dark_mode: bool) -> PropertyValue {
+        let config = ThemeConfig {
+            dark_mode,
+            ..Default::default()
+        };
+        let visuals = if dark_mode {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        };
+        self.resolve(&config, &visuals)
+    }
+}
+
+/// Looks up a property by key (`widgets.hovered.bg_fill`) or by config field
+/// name (`override_widget_hovered_bg_fill`).
+pub fn find_property(key: &str) -> Option<&'static ThemeProperty> {
+    THEME_PROPERTIES
+        .iter()
+        .find(|property| property.key == key || property.field == key)
+}
+
+impl ThemeConfig {
+    /// The override stored for the property `key`, see [`find_property`].
+    pub fn property(&self, key: &str) -> Option<PropertyValue> {
+        find_property(key)?.get(self)
+    }
+
+    pub fn set_property(
+        &mut self,
+        key: &str,
+        value: Option<PropertyValue>,
+    ) -> Result<(), PropertyError> {
+        let property =
+            find_property(key).ok_or_else(|| PropertyError::UnknownProperty(key.to_string()))?;
+        property.set(self, value)
+    }
+
+    /// The value in effect for the property `key`, whether overridden or not.
+    pub fn resolved_property(&self, key: &str) -> Option<PropertyValue> {
+        Some(find_property(key)?.resolve(self, &self.to_visuals()))
+    }
+}
+
+fn to_u8(value: &PropertyValue) -> Option<u8> {
+    match value {
+        PropertyValue::Integer(value) => u8::try_from(*value).ok(),
+        PropertyValue::Float(value) if value.is_finite() => {
+            Some(value.round().clamp(0.0, 255.0) as u8)
+        }
+        _ => None,
+    }
+}
+
+macro_rules! property {
+    (@stored Color) => { PropertyValue::Color };
+    (@stored $kind:ident) => { property!(@wrap $kind) };
+    (@wrap Color) => { PropertyValue::from };
+    (@wrap Float) => { PropertyValue::Float };
+    (@wrap Integer) => { |value: u8| PropertyValue::Integer(value as i64) };
+    (@wrap Bool) => { PropertyValue::Bool };
+    (@unwrap Color) => { PropertyValue::as_color };
+    (@unwrap Float) => { PropertyValue::as_f32 };
+    (@unwrap Integer) => { to_u8 };
+    (@unwrap Bool) => { PropertyValue::as_bool };
+    (
+        $key:expr, $field:ident, $name:expr, $category:ident, $kind:ident,
+        range: $range:expr, advanced: $advanced:literal,
+        |$config:ident, $visuals:ident| $resolve:expr
+    ) => {
+        ThemeProperty {
+            key: $key,
+            field: stringify!($field),
+            name: $name,
+            category: PropertyCategory::$category,
+            kind: PropertyKind::$kind,
+            range: $range,
+            advanced: $advanced,
+            get: |config| config.$field.map(property!(@stored $kind)),
+            set: |config, value| match value {
+                None => {
+                    config.$field = None;
+                    true
+                }
+                Some(value) => match property!(@unwrap $kind)(value) {
+                    Some(value) => {
+                        config.$field = Some(value);
+                        true
+                    }
+                    None => false,
+                },
+            },
+            resolve: |$config, $visuals| property!(@wrap $kind)($resolve),
+        }
+    };
+}
+
+macro_rules! color {
+    ($key:literal, $field:ident, $name:literal, $category:ident, |$config:ident, $visuals:ident| $resolve:expr) => {
+        property!($key, $field, $name, $category, Color, range: None, advanced: false, |$config, $visuals| $resolve)
+    };
+}
+
+macro_rules! widget_state {
+    ($state:ident, $category:ident,
+     $bg_fill:ident, $weak_bg_fill:ident, $bg_stroke_color:ident, $bg_stroke_width:ident,
+     $corner_radius:ident, $fg_stroke_color:ident, $fg_stroke_width:ident, $expansion:ident) => {
+        [
+            property!(
+                concat!("widgets.", stringify!($state), ".bg_fill"), $bg_fill,
+                "Fill", $category, Color, range: None, advanced: false,
+                |_config, visuals| visuals.widgets.$state.bg_fill
+            ),
+            property!(
+                concat!("widgets.", stringify!($state), ".bg_stroke.color"), $bg_stroke_color,
+                "Border", $category, Color, range: None, advanced: false,
+                |_config, visuals| visuals.widgets.$state.bg_stroke.color
+            ),
+            property!(
+                concat!("widgets.", stringify!($state), ".fg_stroke.color"), $fg_stroke_color,
+                "Foreground", $category, Color, range: None, advanced: false,
+                |_config, visuals| visuals.widgets.$state.fg_stroke.color
+            ),
+            property!(
+                concat!("widgets.", stringify!($state), ".weak_bg_fill"), $weak_bg_fill,
+                "Weak Fill", $category, Color, range: None, advanced: true,
+                |_config, visuals| visuals.widgets.$state.weak_bg_fill
+            ),
+            property!(
+                concat!("widgets.", stringify!($state), ".bg_stroke.width"), $bg_stroke_width,
+                "Border Width", $category, Float,
+                range: Some((0.0, 5.0)), advanced: true,
+                |_config, visuals| visuals.widgets.$state.bg_stroke.width
+            ),
+            property!(
+                concat!("widgets.", stringify!($state), ".corner_radius"), $corner_radius,
+                "Corner Radius", $category, Integer,
+                range: Some((0.0, 20.0)), advanced: true,
+                |_config, visuals| visuals.widgets.$state.corner_radius.nw
+            ),
+            property!(
+                concat!("widgets.", stringify!($state), ".fg_stroke.width"), $fg_stroke_width,
+                "Foreground Width", $category, Float,
+                range: Some((0.0, 5.0)), advanced: true,
+                |_config, visuals| visuals.widgets.$state.fg_stroke.width
+            ),
+            property!(
+                concat!("widgets.", stringify!($state), ".expansion"), $expansion,
+                "Expansion", $category, Float,
+                range: Some((-5.0, 10.0)), advanced: true,
+                |_config, visuals| visuals.widgets.$state.expansion
+            ),
+        ]
+    };
+}
+
+const NONINTERACTIVE_PROPERTIES: [ThemeProperty; 8] = widget_state!(
+    noninteractive,
+    NoninteractiveWidgets,
+    override_widget_noninteractive_bg_fill,
+    override_widget_noninteractive_weak_bg_fill,
+    override_widget_noninteractive_bg_stroke_color,
+    override_widget_noninteractive_bg_stroke_width,
+    override_widget_noninteractive_corner_radius,
+    override_widget_noninteractive_fg_stroke_color,
+    override_widget_noninteractive_fg_stroke_width,
+    override_widget_noninteractive_expansion
+);
+
+const INACTIVE_PROPERTIES: [ThemeProperty; 8] = widget_state!(
+    inactive,
+    InactiveWidgets,
+    override_widget_inactive_bg_fill,
+    override_widget_inactive_weak_bg_fill,
+    override_widget_inactive_bg_stroke_color,
+    override_widget_inactive_bg_stroke_width,
+    override_widget_inactive_corner_radius,
+    override_widget_inactive_fg_stroke_color,
+    override_widget_inactive_fg_stroke_width,
+    override_widget_inactive_expansion
+);
+
+const HOVERED_PROPERTIES: [ThemeProperty; 8] = widget_state!(
+    hovered,
+    HoveredWidgets,
+    override_widget_hovered_bg_fill,
+    override_widget_hovered_weak_bg_fill,
+    override_widget_hovered_bg_stroke_color,
+    override_widget_hovered_bg_stroke_width,
+    override_widget_hovered_corner_radius,
+    override_widget_hovered_fg_stroke_color,
+    override_widget_hovered_fg_stroke_width,
+    override_widget_hovered_expansion
+);
+
+const ACTIVE_PROPERTIES: [ThemeProperty; 8] = widget_state!(
+    active,
+    ActiveWidgets,
+    override_widget_active_bg_fill,
+    override_widget_active_weak_bg_fill,
+    override_widget_active_bg_stroke_color,
+    override_widget_active_bg_stroke_width,
+    override_widget_active_corner_radius,
+    override_widget_active_fg_stroke_color,
+    override_widget_active_fg_stroke_width,
+    override_widget_active_expansion
+);
+
+const OPEN_PROPERTIES: [ThemeProperty; 8] = widget_state!(
+    open,
+    OpenWidgets,
+    override_widget_open_bg_fill,
+    override_widget_open_weak_bg_fill,
+    override_widget_open_bg_stroke_color,
+    override_widget_open_bg_stroke_width,
+    override_widget_open_corner_radius,
+    override_widget_open_fg_stroke_color,
+    override_widget_open_fg_stroke_width,
+    override_widget_open_expansion
+);
+
+const TEXT_PROPERTIES: [ThemeProperty; 3] = [
+    color!(
+        "text_color",
+        override_text_color,
+        "Text Color",
+        Text,
+        |_config, visuals| visuals.text_color()
+    ),
+    color!(
+        "weak_text_color",
+        override_weak_text_color,
+        "Weak Text",
+        Text,
+        |_config, visuals| visuals.weak_text_color()
+    ),
+    color!(
+        "hyperlink_color",
+        override_hyperlink_color,
+        "Hyperlink",
+        Text,
+        |_config, visuals| visuals.hyperlink_color
+    ),
+];
+
+const OTHER_PROPERTIES: &[ThemeProperty] = &[
+    color!(
+        "selection.bg_fill",
+        override_selection_bg,
+        "Fill",
+        Selection,
+        |_config, visuals| visuals.selection.bg_fill
+    ),
+    color!(
+        "selection.stroke.color",
+        override_selection_stroke_color,
+        "Border Color",
+        Selection,
+        |_config, visuals| visuals.selection.stroke.color
+    ),
+    property!("selection.stroke.width", override_selection_stroke_width, "Border Width",
+        Selection, Float, range: Some((0.0, 5.0)), advanced: false,
+        |_config, visuals| visuals.selection.stroke.width),
+    color!(
+        "window_fill",
+        override_window_fill,
+        "Window Fill",
+        WindowsAndPanels,
+        |_config, visuals| visuals.window_fill
+    ),
+    color!(
+        "panel_fill",
+        override_panel_fill,
+        "Panel Fill",
+        WindowsAndPanels,
+        |_config, visuals| visuals.panel_fill
+    ),
+    color!(
+        "window_stroke.color",
+        override_window_stroke_color,
+        "Border",
+        WindowsAndPanels,
+        |_config, visuals| visuals.window_stroke.color
+    ),
+    property!("window_stroke.width", override_window_stroke_width, "Border Width",
+        WindowsAndPanels, Float, range: Some((0.0, 5.0)), advanced: false,
+        |_config, visuals| visuals.window_stroke.width),
+    property!("window_corner_radius", override_window_corner_radius, "Corner Radius",
+        WindowsAndPanels, Integer, range: Some((0.0, 20.0)), advanced: false,
+        |_config, visuals| visuals.window_corner_radius.nw),
+    property!("window_shadow.spread", override_window_shadow_size, "Shadow Size",
+        WindowsAndPanels, Integer, range: Some((0.0, 50.0)), advanced: false,
+        |_config, visuals| visuals.window_shadow.spread),
+    property!("popup_shadow.spread", override_popup_shadow_size, "Popup Shadow",
+        WindowsAndPanels, Integer, range: Some((0.0, 50.0)), advanced: false,
+        |_config, visuals| visuals.popup_shadow.spread),
+    color!(
+        "code_bg_color",
+        override_code_bg_color,
+        "Code BG",
+        SpecialColors,
+        |_config, visuals| visuals.code_bg_color
+    ),
+    color!(
+        "faint_bg_color",
+        override_faint_bg_color,
+        "Faint BG",
+        SpecialColors,
+        |_config, visuals| visuals.faint_bg_color
+    ),
+    color!(
+        "extreme_bg_color",
+        override_extreme_bg_color,
+        "Extreme BG",
+        SpecialColors,
+        |_config, visuals| visuals.extreme_bg_color
+    ),
+    color!(
+        "warn_fg_color",
+        override_warn_fg_color,
+        "Warning",
+        SpecialColors,
+        |_config, visuals| visuals.warn_fg_color
+    ),
+    color!(
+        "error_fg_color",
+        override_error_fg_color,
+        "Error",
+        SpecialColors,
+        |_config, visuals| visuals.error_fg_color
+    ),
+    color!(
+        "success_fg_color",
+        override_success_fg_color,
+        "Success",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).success_fg_color
+    ),
+    color!(
+        "success_bg_color",
+        override_success_bg_color,
+        "Success BG",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).success_bg_color
+    ),
+    color!(
+        "info_fg_color",
+        override_info_fg_color,
+        "Info",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).info_fg_color
+    ),
+    color!(
+        "info_bg_color",
+        override_info_bg_color,
+        "Info BG",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).info_bg_color
+    ),
+    color!(
+        "accent_fg_color",
+        override_accent_fg_color,
+        "Accent",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).accent_fg_color
+    ),
+    color!(
+        "accent_bg_color",
+        override_accent_bg_color,
+        "Accent BG",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).accent_bg_color
+    ),
+    color!(
+        "muted_fg_color",
+        override_muted_fg_color,
+        "Muted",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).muted_fg_color
+    ),
+    color!(
+        "muted_bg_color",
+        override_muted_bg_color,
+        "Muted BG",
+        SpecialColors,
+        |config, visuals| config.semantic_colors_with(visuals).muted_bg_color
+    ),
+    property!("button_frame", override_button_frame, "Button Frame", UiOptions, Bool,
+        range: None, advanced: false, |_config, visuals| visuals.button_frame),
+    property!("collapsing_header_frame", override_collapsing_header_frame, "Collapsing Frame",
+        UiOptions, Bool, range: None, advanced: false,
+        |_config, visuals| visuals.collapsing_header_frame),
+    property!("indent_has_left_vline", override_indent_has_left_vline, "Indent Vertical Line",
+        UiOptions, Bool, range: None, advanced: false,
+        |_config, visuals| visuals.indent_has_left_vline),
+    property!("striped", override_striped, "Striped Tables", UiOptions, Bool,
+        range: None, advanced: false, |_config, visuals| visuals.striped),
+    property!("slider_trailing_fill", override_slider_trailing_fill, "Slider Trailing Fill",
+        UiOptions, Bool, range: None, advanced: false,
+        |_config, visuals| visuals.slider_trailing_fill),
+    property!("text_cursor.width", override_text_cursor_width, "Cursor Width", UiOptions,
+        Float, range: Some((1.0, 5.0)), advanced: false,
+        |_config, visuals| visuals.text_cursor.stroke.width),
+    property!("resize_corner_size", override_resize_corner_size, "Resize Corner", UiOptions,
+        Float, range: Some((6.0, 30.0)), advanced: false,
+        |_config, visuals| visuals.resize_corner_size),
+    property!("clip_rect_margin", override_clip_rect_margin, "Clip Margin", UiOptions, Float,
+        range: Some((0.0, 10.0)), advanced: false, |_config, visuals| visuals.clip_rect_margin),
+];
+
+/// The property groups in editor order. Widget states come straight from
+/// `widget_state!`, so a property added there is registered automatically.
+const PROPERTY_GROUPS: &[&[ThemeProperty]] = &[
+    &TEXT_PROPERTIES,
+    &NONINTERACTIVE_PROPERTIES,
+    &INACTIVE_PROPERTIES,
+    &HOVERED_PROPERTIES,
+    &ACTIVE_PROPERTIES,
+    &OPEN_PROPERTIES,
+    OTHER_PROPERTIES,
+];
+
+const fn property_count(groups: &[&[ThemeProperty]]) -> usize {
+    let mut count = 0;
+    let mut index = 0;
+    while index < groups.len() {
+        count += groups[index].len();
+        index += 1;
+    }
+    count
+}
+
+const fn concat_properties<const N: usize>(groups: &[&[ThemeProperty]]) -> [ThemeProperty; N] {
+    let mut properties = [groups[0][0]; N];
+    let mut filled = 0;
+    let mut group = 0;
+    while group < groups.len() {
+        let mut index = 0;
+        while index < groups[group].len() {
+            properties[filled] = groups[group][index];
+            filled += 1;
+            index += 1;
+        }
+        group += 1;
+    }
+    properties
+}
+
+/// Every themable property of [`ThemeConfig`], in editor order.
+pub static THEME_PROPERTIES: &[ThemeProperty] =
+    &concat_properties::<{ property_count(PROPERTY_GROUPS) }>(PROPERTY_GROUPS);
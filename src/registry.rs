@@ -0,0 +1,490 @@
+use crate::config::ThemeConfig;
+use std::path::{Path, PathBuf};
+
+/// Where a registered theme came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ThemeSource {
+    /// One of the presets shipped with this crate.
+    BuiltIn,
+    /// A theme bundled with or registered by the application.
+    App,
+    /// A theme created or installed by the user.
+    User,
+}
+
+impl ThemeSource {
+    pub const ALL: [ThemeSource; 3] = [ThemeSource::BuiltIn, ThemeSource::App, ThemeSource::User];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeSource::BuiltIn => "Built-in",
+            ThemeSource::App => "App",
+            ThemeSource::User => "User",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ThemeEntry {
+    pub id: String,
+    pub source: ThemeSource,
+    pub path: Option<PathBuf>,
+    pub config: ThemeConfig,
+}
+
+/// A user theme with its registry id, as written by [`ThemeRegistry::save_user_themes`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredTheme {
+    pub id: String,
+    pub config: ThemeConfig,
+}
+
+#[derive(Debug)]
+pub enum RegistryError {
+    DuplicateId(String),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::DuplicateId(id) => {
+                write!(f, "a theme with id '{id}' is already registered")
+            }
+            RegistryError::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+impl From<std::io::Error> for RegistryError {
+    fn from(error: std::io::Error) -> Self {
+        RegistryError::Io(error)
+    }
+}
+
+/// Outcome of scanning a directory for theme files.
+///
+/// A single unreadable or duplicate file does not abort discovery; it is
+/// recorded here and the remaining files are still registered.
+#[derive(Debug, Default)]
+pub struct DiscoveryReport {
+    pub loaded: Vec<String>,
+    pub duplicates: Vec<(PathBuf, String)>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// A collection of themes keyed by stable slug ids.
+///
+/// The default registry contains every built-in preset. Additional themes can
+/// be registered directly or discovered from directories of `*.theme.json`
+/// files.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use egui_thematic::{ThemeRegistry, ThemeSource};
+/// use std::path::Path;
+///
+/// let mut registry = ThemeRegistry::default();
+/// let report = registry.discover(Path::new("themes"), ThemeSource::User).unwrap();
+/// for id in &report.loaded {
+///     println!("found {id}");
+/// }
+/// let dracula = registry.get("dracula").unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ThemeRegistry {
+    entries: Vec<ThemeEntry>,
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        Self::with_builtin_presets()
+    }
+}
+
+impl ThemeRegistry {
+    pub const THEME_FILE_EXTENSION: &'static str = ".theme.json";
+
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn with_builtin_presets() -> Self {
+        let mut registry = Self::new();
+        for preset in ThemeConfig::all_presets() {
+            let _ = registry.register(preset, ThemeSource::BuiltIn);
+        }
+        registry
+    }
+
+    /// Turns a theme name into a stable id, e.g. `"Gruvbox Dark"` becomes `"gruvbox-dark"`.
+    pub fn slugify(name: &str) -> String {
+        let mut slug = String::with_capacity(name.len());
+        for character in name.chars() {
+            if character.is_alphanumeric() {
+                slug.extend(character.to_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        while slug.ends_with('-') {
+            slug.pop();
+        }
+        if slug.is_empty() {
+            slug.push_str("theme");
+        }
+        slug
+    }
+
+    /// Registers a theme under the slug of its name and returns the id.
+    pub fn register(
+        &mut self,
+        config: ThemeConfig,
+        source: ThemeSource,
+    ) -> Result<String, RegistryError> {
+        let id = Self::slugify(&config.name);
+        self.register_with_id(id, config, source, None)
+    }
+
+    pub fn register_with_id(
+        &mut self,
+        id: impl Into<String>,
+        config: ThemeConfig,
+        source: ThemeSource,
+        path: Option<PathBuf>,
+    ) -> Result<String, RegistryError> {
+        let id = id.into();
+        if self.contains(&id) {
+            return Err(RegistryError::DuplicateId(id));
+        }
+        self.entries.push(ThemeEntry {
+            id: id.clone(),
+            source,
+            path,
+            config,
+        });
+        Ok(id)
+    }
+
+    /// Loads a single theme file and registers it.
+    pub fn register_file(
+        &mut self,
+        path: &Path,
+        source: ThemeSource,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let config = ThemeConfig::load_from_file(path)?;
+        let id = if config.name.trim().is_empty() {
+            Self::slugify(&Self::file_stem(path))
+        } else {
+            Self::slugify(&config.name)
+        };
+        Ok(self.register_with_id(id, config, source, Some(path.to_path_buf()))?)
+    }
+
+    /// Registers every `*.theme.json` file in `directory`, in file name order.
+    ///
+    /// On the web this lists the themes saved under `directory` in `localStorage`.
+    pub fn discover(
+        &mut self,
+        directory: &Path,
+        source: ThemeSource,
+    ) -> Result<DiscoveryReport, RegistryError> {
+        let mut paths = crate::fs::read_dir(directory)?
+            .into_iter()
+            .filter(|path| Self::is_theme_file(path))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let mut report = DiscoveryReport::default();
+        for path in paths {
+            match self.register_file(&path, source) {
+                Ok(id) => report.loaded.push(id),
+                Err(error) => match error.downcast::<RegistryError>() {
+                    Ok(error) => match *error {
+                        RegistryError::DuplicateId(id) => report.duplicates.push((path, id)),
+                        RegistryError::Io(error) => report.failed.push((path, error.to_string())),
+                    },
+                    Err(error) => report.failed.push((path, error.to_string())),
+                },
+            }
+        }
+        Ok(report)
+    }
+
+    pub fn is_theme_file(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.to_ascii_lowercase()
+                    .ends_with(Self::THEME_FILE_EXTENSION)
+            })
+    }
+
+    fn file_stem(path: &Path) -> String {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        name.strip_suffix(Self::THEME_FILE_EXTENSION)
+            .unwrap_or(name)
+            .to_string()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ThemeEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut ThemeEntry> {
+        self.entries.iter_mut().find(|entry| entry.id == id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Finds a theme by display name, ignoring case.
+    pub fn find_by_name(&self, name: &str) -> Option<&ThemeEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.config.name.eq_ignore_ascii_case(name))
+    }
+
+    /// An id derived from `name` that is not registered yet, e.g. `nord-2`.
+    pub fn unique_id(&self, name: &str) -> String {
+        let slug = Self::slugify(name);
+        let mut id = slug.clone();
+        let mut suffix = 2;
+        while self.contains(&id) {
+            id = format!("{slug}-{suffix}");
+            suffix += 1;
+        }
+        id
+    }
+
+    /// Moves `id` by `offset` places among the entries with the same source.
+    ///
+    /// Returns `false` if the entry does not exist or is already at that end.
+    pub fn move_entry(&mut self, id: &str, offset: isize) -> bool {
+        let Some(entry) = self.get(id) else {
+            return false;
+        };
+        let source = entry.source;
+        let positions = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.source == source)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let Some(current) = positions
+            .iter()
+            .position(|index| self.entries[*index].id == id)
+        else {
+            return false;
+        };
+        let Some(target) = current
+            .checked_add_signed(offset)
+            .filter(|target| *target < positions.len())
+        else {
+            return false;
+        };
+        if target == current {
+            return false;
+        }
+        let entry = self.entries.remove(positions[current]);
+        self.entries.insert(positions[target], entry);
+        true
+    }
+
+    /// Every [`ThemeSource::User`] theme, in order.
+    pub fn user_themes(&self) -> Vec<StoredTheme> {
+        self.by_source(ThemeSource::User)
+            .map(|entry| StoredTheme {
+                id: entry.id.clone(),
+                config: entry.config.clone(),
+            })
+            .collect()
+    }
+
+    /// Registers `themes` as user themes, skipping ids that are already
+    /// registered. Returns the ids that were registered.
+    pub fn register_user_themes(&mut self, themes: Vec<StoredTheme>) -> Vec<String> {
+        themes
+            .into_iter()
+            .filter_map(|theme| {
+                self.register_with_id(theme.id, theme.config, ThemeSource::User, None)
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Writes every [`ThemeSource::User`] theme to a single JSON file, in order.
+    pub fn save_user_themes(&self, path: &Path) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string_pretty(&self.user_themes())?;
+        crate::fs::write(path, &json)
+    }
+
+    /// Registers the themes saved by [`Self::save_user_themes`] as user themes.
+    ///
+    /// Themes whose id is already registered are skipped. Returns the ids
+    /// that were registered.
+    pub fn load_user_themes(
+        &mut self,
+        path: &Path,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let json = crate::fs::read_to_string(path)?;
+        let themes: Vec<StoredTheme> = serde_json::from_str(&json)?;
+        Ok(self.register_user_themes(themes))
+    }
+
+    /// Every tag used by a registered theme, sorted and without duplicates.
+    pub fn tags(&self) -> Vec<String> {
+        let tags = self
+            .entries
+            .iter()
+            .flat_map(|entry| entry.config.tags.iter().map(|tag| tag.to_lowercase()))
+            .collect::<std::collections::BTreeSet<_>>();
+        tags.into_iter().collect()
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<ThemeEntry> {
+        let index = self.entries.iter().position(|entry| entry.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    pub fn entries(&self) -> &[ThemeEntry] {
+        &self.entries
+    }
+
+    pub fn by_source(&self, source: ThemeSource) -> impl Iterator<Item = &ThemeEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.source == source)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.id.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_names() {
+        assert_eq!(ThemeRegistry::slugify("Gruvbox Dark"), "gruvbox-dark");
+        assert_eq!(ThemeRegistry::slugify("  One -- Dark! "), "one-dark");
+        assert_eq!(
+            ThemeRegistry::slugify("Catppuccin: Mocha"),
+            "catppuccin-mocha"
+        );
+        assert_eq!(ThemeRegistry::slugify("***"), "theme");
+    }
+
+    #[test]
+    fn discover_registers_theme_files_in_name_order() {
+        let directory =
+            std::env::temp_dir().join(format!("egui_thematic_discover_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let write = |file: &str, config: &ThemeConfig| {
+            config.save_to_file(&directory.join(file)).unwrap();
+        };
+        let mut zebra = ThemeConfig::nord_preset();
+        zebra.name = "Zebra".to_string();
+        write("b.theme.json", &zebra);
+        write("a.theme.json", &ThemeConfig::nord_preset());
+        write("c.theme.json", &ThemeConfig::nord_preset());
+        write("notes.json", &ThemeConfig::dracula_preset());
+        std::fs::write(directory.join("d.theme.json"), "not json").unwrap();
+
+        let mut registry = ThemeRegistry::new();
+        let report = registry.discover(&directory, ThemeSource::App).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(report.loaded, ["nord", "zebra"]);
+        assert_eq!(
+            report.duplicates,
+            [(directory.join("c.theme.json"), "nord".to_string())]
+        );
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, directory.join("d.theme.json"));
+        assert!(!registry.contains("dracula"));
+    }
+
+    fn user_registry(names: &[&str]) -> ThemeRegistry {
+        let mut registry = ThemeRegistry::default();
+        for name in names {
+            let config = ThemeConfig {
+                name: name.to_string(),
+                ..Default::default()
+            };
+            let id = registry.unique_id(name);
+            registry
+                .register_with_id(id, config, ThemeSource::User, None)
+                .unwrap();
+        }
+        registry
+    }
+
+    fn user_ids(registry: &ThemeRegistry) -> Vec<String> {
+        registry
+            .user_themes()
+            .into_iter()
+            .map(|theme| theme.id)
+            .collect()
+    }
+
+    #[test]
+    fn unique_id_skips_taken_slugs() {
+        let registry = user_registry(&["Nord", "Nord", "nord!"]);
+        assert_eq!(user_ids(&registry), ["nord-2", "nord-3", "nord-4"]);
+        assert_eq!(registry.unique_id("Mine"), "mine");
+    }
+
+    #[test]
+    fn move_entry_stays_within_its_source() {
+        let mut registry = user_registry(&["A", "B", "C"]);
+        assert!(!registry.move_entry("a", -1));
+        assert!(!registry.move_entry("c", 1));
+        assert!(!registry.move_entry("b", 0));
+        assert!(!registry.move_entry("missing", 1));
+
+        assert!(registry.move_entry("a", 2));
+        assert_eq!(user_ids(&registry), ["b", "c", "a"]);
+        assert!(registry.move_entry("a", -1));
+        assert_eq!(user_ids(&registry), ["b", "a", "c"]);
+        assert_eq!(registry.entries()[0].id, "dark");
+    }
+
+    #[test]
+    fn user_themes_round_trip_through_a_file() {
+        let path = std::env::temp_dir().join(format!(
+            "egui_thematic_user_themes_{}.json",
+            std::process::id()
+        ));
+        let registry = user_registry(&["B", "A"]);
+        registry.save_user_themes(&path).unwrap();
+
+        let mut loaded = user_registry(&["A"]);
+        let registered = loaded.load_user_themes(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(registered, ["b"]);
+        assert_eq!(user_ids(&loaded), ["a", "b"]);
+        assert_eq!(loaded.get("b").unwrap().config.name, "B");
+    }
+}
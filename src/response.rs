@@ -0,0 +1,138 @@
+use crate::config::ThemeConfig;
+use std::path::PathBuf;
+
+/// A discrete action taken in the theme editor.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThemeEditorEvent {
+    /// A registry theme was selected from the preset list.
+    PresetSelected {
+        id: String,
+    },
+    Randomized,
+    LoadedFromFile {
+        path: PathBuf,
+    },
+    SavedToFile {
+        path: PathBuf,
+    },
+    /// A dropped theme file was added to the registry.
+    PresetAdded {
+        id: String,
+    },
+    /// Every property was reset to the base theme.
+    Reset,
+    /// Previewed changes were applied to the app.
+    Applied,
+    /// Previewed or applied changes were discarded.
+    Reverted,
+    Undone,
+    Redone,
+}
+
+/// What happened in the theme editor during one frame.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let response = render_theme_editor(ui, &mut editor_state);
+/// if response.changed {
+///     document.mark_dirty();
+/// }
+/// for event in &response.events {
+///     if let ThemeEditorEvent::PresetSelected { id } = event {
+///         settings.preset = id.clone();
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ThemeEditorResponse {
+    /// Whether the current theme is different from the start of the frame.
+    pub changed: bool,
+    /// Keys of the properties that changed, in the form used by [`crate::ThemeDiff`].
+    pub changed_properties: Vec<String>,
+    pub events: Vec<ThemeEditorEvent>,
+}
+
+impl ThemeEditorResponse {
+    pub(crate) fn between(
+        before: &ThemeConfig,
+        after: &ThemeConfig,
+        events: Vec<ThemeEditorEvent>,
+    ) -> Self {
+        let changed_properties = if before == after {
+            Vec::new()
+        } else {
+            before
+                .diff(after)
+                .properties
+                .into_iter()
+                .map(|property| property.key)
+                .collect()
+        };
+        Self {
+            changed: !changed_properties.is_empty(),
+            changed_properties,
+            events,
+        }
+    }
+
+    pub fn property_changed(&self, key: &str) -> bool {
+        self.changed_properties.iter().any(|changed| changed == key)
+    }
+
+    pub fn has_event(&self, event: &ThemeEditorEvent) -> bool {
+        self.events.contains(event)
+    }
+}
+
+type ChangeHook = Box<dyn FnMut(&ThemeConfig, &ThemeEditorResponse)>;
+type EventHook = Box<dyn FnMut(&ThemeEditorEvent)>;
+
+/// Callbacks run by the theme editor, for apps that prefer them over
+/// inspecting [`ThemeEditorResponse`].
+///
+/// # Example
+///
+/// ```rust
+/// use egui_thematic::{ThemeEditorHooks, ThemeEditorState};
+///
+/// let mut editor_state = ThemeEditorState::default();
+/// editor_state.hooks = ThemeEditorHooks::default()
+///     .on_change(|config, _response| println!("theme is now '{}'", config.name))
+///     .on_event(|event| println!("{event:?}"));
+/// ```
+#[derive(Default)]
+pub struct ThemeEditorHooks {
+    on_change: Option<ChangeHook>,
+    on_event: Option<EventHook>,
+}
+
+impl ThemeEditorHooks {
+    /// Called with the current theme once per frame in which it changed.
+    pub fn on_change(
+        mut self,
+        hook: impl FnMut(&ThemeConfig, &ThemeEditorResponse) + 'static,
+    ) -> Self {
+        self.on_change = Some(Box::new(hook));
+        self
+    }
+
+    /// Called once for every [`ThemeEditorEvent`].
+    pub fn on_event(mut self, hook: impl FnMut(&ThemeEditorEvent) + 'static) -> Self {
+        self.on_event = Some(Box::new(hook));
+        self
+    }
+
+    pub(crate) fn run(&mut self, config: &ThemeConfig, response: &ThemeEditorResponse) {
+        if let Some(on_event) = &mut self.on_event {
+            for event in &response.events {
+                on_event(event);
+            }
+        }
+        if response.changed {
+            if let Some(on_change) = &mut self.on_change {
+                on_change(config, response);
+            }
+        }
+    }
+}
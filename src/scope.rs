@@ -0,0 +1,68 @@
+use crate::config::ThemeConfig;
+
+impl ThemeConfig {
+    /// Applies this theme to `style`, leaving non-visual settings such as spacing untouched.
+    pub fn apply_to_style(&self, style: &mut egui::Style) {
+        style.visuals = self.to_visuals();
+    }
+}
+
+/// Renders `add_contents` with `theme` applied to a child [`egui::Ui`] only.
+///
+/// The rest of the UI keeps the global theme, and the previous style is in
+/// effect again once `add_contents` returns. Scopes can be nested; the
+/// innermost theme wins.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// ui.label("Uses the app theme");
+/// with_theme(ui, &ThemeConfig::light_preset(), |ui| {
+///     ui.label("Uses the light theme");
+///     with_theme(ui, &danger_theme, |ui| {
+///         if ui.button("Delete everything").clicked() {
+///             // ...
+///         }
+///     });
+/// });
+/// ```
+pub fn with_theme<R>(
+    ui: &mut egui::Ui,
+    theme: &ThemeConfig,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> egui::InnerResponse<R> {
+    ui.scope(|ui| {
+        theme.apply_to_style(ui.style_mut());
+        add_contents(ui)
+    })
+}
+
+/// Like [`with_theme`], but also paints the theme's panel background behind the contents.
+///
+/// Use this for embedded regions such as inspectors or previews that should
+/// look like a separate surface rather than widgets floating on the parent.
+pub fn themed_frame<R>(
+    ui: &mut egui::Ui,
+    theme: &ThemeConfig,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> egui::InnerResponse<R> {
+    with_theme(ui, theme, |ui| {
+        let visuals = ui.visuals();
+        egui::Frame::new()
+            .fill(visuals.panel_fill)
+            .stroke(visuals.widgets.noninteractive.bg_stroke)
+            .corner_radius(visuals.widgets.noninteractive.corner_radius)
+            .inner_margin(8.0)
+            .show(ui, add_contents)
+            .inner
+    })
+}
+
+/// A window frame drawn with `theme`, for use with [`egui::Window::frame`].
+///
+/// Pair it with [`with_theme`] inside the window so the contents match the frame.
+pub fn themed_window_frame(ctx: &egui::Context, theme: &ThemeConfig) -> egui::Frame {
+    let mut style = (*ctx.style()).clone();
+    theme.apply_to_style(&mut style);
+    egui::Frame::window(&style)
+}
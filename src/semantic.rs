@@ -0,0 +1,90 @@
+use crate::config::ThemeConfig;
+use egui::{Color32, Visuals};
+
+/// Resolved colors for status and emphasis roles, for use in app code.
+///
+/// Roles the theme does not override fall back to colors derived from its
+/// visuals, so every field is always usable.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_thematic::ThemeConfig;
+///
+/// let colors = ThemeConfig::nord_preset().semantic_colors();
+/// assert_eq!(colors.success_fg_color.to_array(), [163, 190, 140, 255]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SemanticColors {
+    pub warn_fg_color: Color32,
+    pub error_fg_color: Color32,
+    pub success_fg_color: Color32,
+    pub success_bg_color: Color32,
+    pub info_fg_color: Color32,
+    pub info_bg_color: Color32,
+    pub accent_fg_color: Color32,
+    pub accent_bg_color: Color32,
+    pub muted_fg_color: Color32,
+    pub muted_bg_color: Color32,
+}
+
+impl ThemeConfig {
+    pub fn semantic_colors(&self) -> SemanticColors {
+        self.semantic_colors_with(&self.to_visuals())
+    }
+
+    /// Resolves the semantic roles against already computed visuals for this theme.
+    pub fn semantic_colors_with(&self, visuals: &Visuals) -> SemanticColors {
+        let color = |color: [u8; 4]| {
+            Color32::from_rgba_unmultiplied(color[0], color[1], color[2], color[3])
+        };
+        let background = |fg: Color32| visuals.panel_fill.lerp_to_gamma(fg, 0.2);
+        let (success, info) = if visuals.dark_mode {
+            (
+                Color32::from_rgb(120, 200, 120),
+                Color32::from_rgb(100, 170, 240),
+            )
+        } else {
+            (
+                Color32::from_rgb(30, 130, 60),
+                Color32::from_rgb(30, 100, 200),
+            )
+        };
+
+        let success_fg_color = self.override_success_fg_color.map(color).unwrap_or(success);
+        let info_fg_color = self.override_info_fg_color.map(color).unwrap_or(info);
+        let accent_fg_color = self
+            .override_accent_fg_color
+            .map(color)
+            .unwrap_or(visuals.hyperlink_color);
+        let muted_fg_color = self
+            .override_muted_fg_color
+            .map(color)
+            .unwrap_or(visuals.weak_text_color());
+
+        SemanticColors {
+            warn_fg_color: visuals.warn_fg_color,
+            error_fg_color: visuals.error_fg_color,
+            success_fg_color,
+            success_bg_color: self
+                .override_success_bg_color
+                .map(color)
+                .unwrap_or_else(|| background(success_fg_color)),
+            info_fg_color,
+            info_bg_color: self
+                .override_info_bg_color
+                .map(color)
+                .unwrap_or_else(|| background(info_fg_color)),
+            accent_fg_color,
+            accent_bg_color: self
+                .override_accent_bg_color
+                .map(color)
+                .unwrap_or_else(|| background(accent_fg_color)),
+            muted_fg_color,
+            muted_bg_color: self
+                .override_muted_bg_color
+                .map(color)
+                .unwrap_or(visuals.faint_bg_color),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use crate::config::ThemeConfig;
+use crate::history::ThemeHistory;
+use crate::persistence::ThemeStorage;
+use crate::registry::StoredTheme;
+use crate::state::ThemeEditorState;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The key used by [`ThemeEditorState::save_session_to_storage`].
+pub const SESSION_STORAGE_KEY: &str = "egui_thematic_session";
+
+/// Everything needed to resume editing where the user left off.
+///
+/// Unlike [`crate::PersistedTheme`], which only remembers the active theme,
+/// this keeps the work in progress: the edited theme and the preset it is
+/// based on, user presets, undo history, recent files and the editor layout.
+/// Built-in and app themes are not included; they come from the registry
+/// the app builds on startup.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_thematic::{ThemeEditorSession, ThemeEditorState};
+///
+/// let editor_state = ThemeEditorState::default();
+/// let json = editor_state.session().to_json().unwrap();
+///
+/// let mut restored = ThemeEditorState::default();
+/// restored.restore_session(ThemeEditorSession::from_json(&json).unwrap());
+/// ```
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ThemeEditorSession {
+    pub current_config: ThemeConfig,
+    /// The registry theme the edits are based on.
+    pub selected_theme_id: Option<String>,
+    /// The theme as last opened or saved, for the unsaved changes marker.
+    pub saved_config: ThemeConfig,
+    pub user_presets: Vec<StoredTheme>,
+    pub history: ThemeHistory,
+    pub current_file: Option<PathBuf>,
+    pub recent_files: Vec<PathBuf>,
+    /// Whether each collapsible section of the editor was open, by section key.
+    pub open_sections: BTreeMap<String, bool>,
+    /// Vertical scroll position of the editor, in points.
+    pub scroll_offset: f32,
+    pub simple_mode: bool,
+    pub property_filter: String,
+    pub show_only_overridden: bool,
+    pub compare_theme_id: Option<String>,
+    pub gallery_tag: Option<String>,
+
+    pub storybook_checkbox: bool,
+    pub storybook_radio: i32,
+    pub storybook_slider: f32,
+    pub storybook_text: String,
+    pub storybook_combo_selected: usize,
+}
+
+/// The session of a fresh editor. Fields missing from a stored session, e.g.
+/// one saved by an older version, take their values from here.
+impl Default for ThemeEditorSession {
+    fn default() -> Self {
+        Self::from_editor(&ThemeEditorState::default())
+    }
+}
+
+impl ThemeEditorSession {
+    pub fn from_editor(editor_state: &ThemeEditorState) -> Self {
+        Self {
+            current_config: editor_state.current_config.clone(),
+            selected_theme_id: editor_state.selected_theme_id.clone(),
+            saved_config: editor_state.saved_config.clone(),
+            user_presets: editor_state.registry.user_themes(),
+            history: editor_state.history.clone(),
+            current_file: editor_state.current_file.clone(),
+            recent_files: editor_state.recent_files.clone(),
+            open_sections: editor_state.open_sections.clone(),
+            scroll_offset: editor_state.scroll_offset,
+            simple_mode: editor_state.simple_mode,
+            property_filter: editor_state.property_filter.clone(),
+            show_only_overridden: editor_state.show_only_overridden,
+            compare_theme_id: editor_state.compare_theme_id.clone(),
+            gallery_tag: editor_state.gallery_tag.clone(),
+
+            storybook_checkbox: editor_state.storybook_checkbox,
+            storybook_radio: editor_state.storybook_radio,
+            storybook_slider: editor_state.storybook_slider,
+            storybook_text: editor_state.storybook_text.clone(),
+            storybook_combo_selected: editor_state.storybook_combo_selected,
+        }
+    }
+
+    /// Restores this session into the editor.
+    ///
+    /// User presets missing from the registry are registered. If the base
+    /// preset is no longer registered the theme is kept as a custom theme and
+    /// the undo history, which refers to that preset, is cleared.
+    pub fn apply_to_editor(self, editor_state: &mut ThemeEditorState) {
+        editor_state
+            .registry
+            .register_user_themes(self.user_presets);
+
+        let base_missing = self
+            .selected_theme_id
+            .as_deref()
+            .is_some_and(|id| !editor_state.registry.contains(id));
+        editor_state.current_config = self.current_config;
+        editor_state.selected_theme_id = self.selected_theme_id.filter(|_| !base_missing);
+        editor_state.history = self.history;
+        if base_missing {
+            editor_state.clear_history();
+        }
+        editor_state.commit_current();
+
+        editor_state.saved_config = self.saved_config;
+        editor_state.current_file = self.current_file;
+        editor_state.recent_files = self.recent_files;
+        editor_state.open_sections = self.open_sections;
+        editor_state.scroll_offset = self.scroll_offset;
+        editor_state.restore_layout = true;
+        editor_state.simple_mode = self.simple_mode;
+        editor_state.property_filter = self.property_filter;
+        editor_state.show_only_overridden = self.show_only_overridden;
+        editor_state.compare_theme_id = self.compare_theme_id;
+        editor_state.gallery_tag = self.gallery_tag;
+
+        editor_state.storybook_checkbox = self.storybook_checkbox;
+        editor_state.storybook_radio = self.storybook_radio;
+        editor_state.storybook_slider = self.storybook_slider;
+        editor_state.storybook_text = self.storybook_text;
+        editor_state.storybook_combo_selected = self.storybook_combo_selected;
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl ThemeEditorState {
+    pub fn session(&self) -> ThemeEditorSession {
+        ThemeEditorSession::from_editor(self)
+    }
+
+    pub fn restore_session(&mut self, session: ThemeEditorSession) {
+        session.apply_to_editor(self);
+    }
+
+    /// Saves the editor session to `storage`. Call this when the app shuts down.
+    pub fn save_session_to_storage(&self, storage: &mut dyn ThemeStorage) {
+        if let Ok(json) = self.session().to_json() {
+            storage.set_string(SESSION_STORAGE_KEY, json);
+        }
+    }
+
+    /// Restores the session saved by [`Self::save_session_to_storage`]. Call this at startup.
+    ///
+    /// Returns `false` if nothing was stored or the stored session could not be read.
+    pub fn load_session_from_storage(&mut self, storage: &dyn ThemeStorage) -> bool {
+        let Some(session) = storage
+            .get_string(SESSION_STORAGE_KEY)
+            .and_then(|json| ThemeEditorSession::from_json(&json).ok())
+        else {
+            return false;
+        };
+        self.restore_session(session);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_round_trip() {
+        let mut editor_state = ThemeEditorState::default();
+        editor_state.create_user_preset("Mine");
+        editor_state.current_config.override_text_color = Some([1, 2, 3, 255]);
+        editor_state.record_change("Text color".to_string());
+        editor_state.recent_files.push(PathBuf::from(format!(
+            "mine{}",
+            crate::ThemeRegistry::THEME_FILE_EXTENSION
+        )));
+        editor_state.open_sections.insert("text".to_string(), false);
+        editor_state.scroll_offset = 120.0;
+        editor_state.simple_mode = true;
+        editor_state.storybook_text = "Edited".to_string();
+
+        let json = editor_state.session().to_json().unwrap();
+        let session = ThemeEditorSession::from_json(&json).unwrap();
+        assert_eq!(session.to_json().unwrap(), json);
+
+        let mut restored = ThemeEditorState::default();
+        restored.restore_session(session);
+        assert_eq!(restored.current_config, editor_state.current_config);
+        assert_eq!(restored.selected_theme_id, editor_state.selected_theme_id);
+        assert!(restored.is_user_preset("mine"));
+        assert!(restored.history.can_undo());
+        assert_eq!(restored.recent_files, editor_state.recent_files);
+        assert_eq!(restored.open_sections, editor_state.open_sections);
+        assert_eq!(restored.scroll_offset, 120.0);
+        assert!(restored.simple_mode);
+        assert_eq!(restored.storybook_text, "Edited");
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let session = ThemeEditorSession::from_json(r#"{"simple_mode": true}"#).unwrap();
+        assert!(session.simple_mode);
+        assert_eq!(
+            session.storybook_text,
+            ThemeEditorSession::default().storybook_text
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::config::ThemeConfig;
+use crate::properties::find_property;
+use crate::value::PropertyValue;
+use egui::{Color32, Visuals};
+
+/// One of the eight colors edited in the simple editor mode.
+///
+/// Each core color writes a group of properties: the first one takes the color
+/// as is and the rest are derived shades of it, see [`CoreColor::properties`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CoreColor {
+    Background,
+    Surface,
+    Primary,
+    Text,
+    WeakText,
+    WidgetFill,
+    WidgetHover,
+    WidgetActive,
+}
+
+impl CoreColor {
+    pub const ALL: [CoreColor; 8] = [
+        CoreColor::Background,
+        CoreColor::Surface,
+        CoreColor::Primary,
+        CoreColor::Text,
+        CoreColor::WeakText,
+        CoreColor::WidgetFill,
+        CoreColor::WidgetHover,
+        CoreColor::WidgetActive,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CoreColor::Background => "Background",
+            CoreColor::Surface => "Surface",
+            CoreColor::Primary => "Primary",
+            CoreColor::Text => "Text",
+            CoreColor::WeakText => "Weak Text",
+            CoreColor::WidgetFill => "Widget Fill",
+            CoreColor::WidgetHover => "Widget Hover",
+            CoreColor::WidgetActive => "Widget Active",
+        }
+    }
+
+    /// The property keys written by this color with how far each is shifted.
+    ///
+    /// Positive shifts blend toward white in dark mode and black in light mode,
+    /// so the shade stands out more; negative shifts blend the other way.
+    pub fn properties(&self) -> &'static [(&'static str, f32)] {
+        match self {
+            CoreColor::Background => &[
+                ("panel_fill", 0.0),
+                ("faint_bg_color", 0.04),
+                ("code_bg_color", 0.08),
+                ("extreme_bg_color", -0.4),
+            ],
+            CoreColor::Surface => &[
+                ("window_fill", 0.0),
+                ("widgets.noninteractive.bg_fill", 0.0),
+                ("widgets.noninteractive.weak_bg_fill", 0.0),
+                ("window_stroke.color", 0.15),
+                ("widgets.noninteractive.bg_stroke.color", 0.15),
+            ],
+            CoreColor::Primary => &[("selection.bg_fill", 0.0), ("hyperlink_color", 0.3)],
+            CoreColor::Text => &[
+                ("text_color", 0.0),
+                ("widgets.noninteractive.fg_stroke.color", 0.0),
+                ("widgets.inactive.fg_stroke.color", 0.0),
+                ("widgets.open.fg_stroke.color", 0.0),
+                ("widgets.hovered.fg_stroke.color", 0.3),
+                ("widgets.active.fg_stroke.color", 0.5),
+                ("selection.stroke.color", 0.3),
+            ],
+            CoreColor::WeakText => &[("weak_text_color", 0.0)],
+            CoreColor::WidgetFill => &[
+                ("widgets.inactive.bg_fill", 0.0),
+                ("widgets.inactive.weak_bg_fill", 0.0),
+                ("widgets.open.bg_fill", 0.0),
+                ("widgets.open.weak_bg_fill", 0.0),
+            ],
+            CoreColor::WidgetHover => &[
+                ("widgets.hovered.bg_fill", 0.0),
+                ("widgets.hovered.weak_bg_fill", 0.0),
+                ("widgets.hovered.bg_stroke.color", 0.3),
+            ],
+            CoreColor::WidgetActive => &[
+                ("widgets.active.bg_fill", 0.0),
+                ("widgets.active.weak_bg_fill", 0.0),
+                ("widgets.active.bg_stroke.color", 0.4),
+            ],
+        }
+    }
+}
+
+/// One of the sliders in the simple editor mode, each setting a group of
+/// numeric properties to the same value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GlobalControl {
+    CornerRadius,
+    BorderWidth,
+    WindowShadow,
+}
+
+impl GlobalControl {
+    pub const ALL: [GlobalControl; 3] = [
+        GlobalControl::CornerRadius,
+        GlobalControl::BorderWidth,
+        GlobalControl::WindowShadow,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GlobalControl::CornerRadius => "Corner Radius",
+            GlobalControl::BorderWidth => "Border Width",
+            GlobalControl::WindowShadow => "Window Shadow",
+        }
+    }
+
+    pub fn properties(&self) -> &'static [&'static str] {
+        match self {
+            GlobalControl::CornerRadius => &[
+                "widgets.inactive.corner_radius",
+                "widgets.hovered.corner_radius",
+                "widgets.active.corner_radius",
+                "widgets.open.corner_radius",
+                "widgets.noninteractive.corner_radius",
+                "window_corner_radius",
+            ],
+            GlobalControl::BorderWidth => &[
+                "widgets.inactive.bg_stroke.width",
+                "widgets.hovered.bg_stroke.width",
+                "widgets.active.bg_stroke.width",
+                "widgets.open.bg_stroke.width",
+                "widgets.noninteractive.bg_stroke.width",
+                "window_stroke.width",
+            ],
+            GlobalControl::WindowShadow => &["window_shadow.spread", "popup_shadow.spread"],
+        }
+    }
+
+    pub fn range(&self) -> std::ops::RangeInclusive<f32> {
+        match self {
+            GlobalControl::CornerRadius => 0.0..=20.0,
+            GlobalControl::BorderWidth => 0.0..=5.0,
+            GlobalControl::WindowShadow => 0.0..=50.0,
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// The value in effect for `color`, given visuals already computed from this theme.
+    pub fn core_color(&self, visuals: &Visuals, color: CoreColor) -> Color32 {
+        let (key, _) = color.properties()[0];
+        find_property(key)
+            .and_then(|property| property.resolve(self, visuals).as_color32())
+            .unwrap_or_default()
+    }
+
+    /// Overrides every property derived from `color`.
+    pub fn set_core_color(&mut self, color: CoreColor, value: Color32) {
+        let (stronger, weaker) = if self.dark_mode {
+            (Color32::WHITE, Color32::BLACK)
+        } else {
+            (Color32::BLACK, Color32::WHITE)
+        };
+        for &(key, shift) in color.properties() {
+            let shade = if shift >= 0.0 {
+                value.lerp_to_gamma(stronger, shift)
+            } else {
+                value.lerp_to_gamma(weaker, -shift)
+            };
+            let _ = self.set_property(key, Some(PropertyValue::from(shade)));
+        }
+    }
+
+    /// The value in effect for the first property of `control`.
+    pub fn global_control(&self, visuals: &Visuals, control: GlobalControl) -> f32 {
+        find_property(control.properties()[0])
+            .and_then(|property| property.resolve(self, visuals).as_f32())
+            .unwrap_or_default()
+    }
+
+    /// The value in effect for each property of `control`.
+    pub fn global_control_values(
+        &self,
+        visuals: &Visuals,
+        control: GlobalControl,
+    ) -> Vec<(&'static str, f32)> {
+        control
+            .properties()
+            .iter()
+            .filter_map(|key| {
+                let value = find_property(key)?.resolve(self, visuals).as_f32()?;
+                Some((*key, value))
+            })
+            .collect()
+    }
+
+    /// Whether the properties of `control` were overridden with different
+    /// values, e.g. one widget state edited on its own.
+    ///
+    /// Properties left at egui's defaults do not count, so the usual
+    /// difference between window and widget defaults is not mixed.
+    pub fn is_global_control_mixed(&self, control: GlobalControl) -> bool {
+        let overrides = control
+            .properties()
+            .iter()
+            .filter_map(|key| self.property(key)?.as_f32())
+            .collect::<Vec<_>>();
+        overrides.windows(2).any(|pair| pair[0] != pair[1])
+    }
+
+    /// Overrides every property of `control` with `value`.
+    pub fn set_global_control(&mut self, control: GlobalControl, value: f32) {
+        for key in control.properties() {
+            let _ = self.set_property(key, Some(PropertyValue::Float(value)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_not_mixed() {
+        for config in ThemeConfig::all_presets() {
+            for control in GlobalControl::ALL {
+                assert!(
+                    !config.is_global_control_mixed(control),
+                    "{} {}",
+                    config.name,
+                    control.label()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn differing_overrides_are_mixed() {
+        let mut config = ThemeConfig::default();
+        config.set_global_control(GlobalControl::CornerRadius, 4.0);
+        assert!(!config.is_global_control_mixed(GlobalControl::CornerRadius));
+
+        let _ = config.set_property(
+            "widgets.hovered.corner_radius",
+            Some(PropertyValue::Float(8.0)),
+        );
+        assert!(config.is_global_control_mixed(GlobalControl::CornerRadius));
+    }
+}
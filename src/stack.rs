@@ -0,0 +1,239 @@
+use crate::config::ThemeConfig;
+use crate::context::ThemeContextExt;
+use crate::properties::THEME_PROPERTIES;
+use crate::variants::{VariantStateStyle, WidgetVariant};
+use std::sync::Mutex;
+
+/// A named partial theme applied on top of a base theme.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeLayer {
+    pub name: String,
+    pub config: ThemeConfig,
+}
+
+/// An ordered stack of overlay layers resolved on top of a base theme.
+///
+/// Each layer is a partial [`ThemeConfig`]: every override it sets wins over
+/// the base and over earlier layers, and every `None` leaves the value below
+/// untouched. The base theme's name and dark mode are always kept.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut overlays = ThemeStack::default();
+///
+/// // Tint the UI red while connected to production
+/// overlays.push(
+///     "production",
+///     ThemeConfig {
+///         override_panel_fill: Some([60, 10, 10, 255]),
+///         override_window_stroke_color: Some([220, 40, 40, 255]),
+///         ..Default::default()
+///     },
+/// );
+///
+/// // Every frame
+/// overlays.apply(ctx, &user_theme);
+///
+/// // Later
+/// overlays.remove("production");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ThemeStack {
+    layers: Vec<ThemeLayer>,
+    generation: u64,
+    resolved: ResolvedCache,
+}
+
+/// The result of the last [`ThemeStack::apply`], with the generation and base
+/// it was resolved from.
+#[derive(Debug, Default)]
+struct ResolvedCache(Mutex<Option<(u64, ThemeConfig, ThemeConfig)>>);
+
+impl Clone for ResolvedCache {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.lock().clone()))
+    }
+}
+
+impl ResolvedCache {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<(u64, ThemeConfig, ThemeConfig)>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ThemeStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes an overlay on top of the stack.
+    ///
+    /// A layer with the same name is replaced in place instead of being duplicated.
+    pub fn push(&mut self, name: impl Into<String>, config: ThemeConfig) {
+        let name = name.into();
+        match self.layers.iter_mut().find(|layer| layer.name == name) {
+            Some(layer) => layer.config = config,
+            None => self.layers.push(ThemeLayer { name, config }),
+        }
+        self.generation += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<ThemeLayer> {
+        self.generation += 1;
+        self.layers.pop()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ThemeLayer> {
+        let index = self.layers.iter().position(|layer| layer.name == name)?;
+        self.generation += 1;
+        Some(self.layers.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.generation += 1;
+        self.layers.clear();
+    }
+
+    /// A counter that changes whenever layers are added, replaced or removed.
+    ///
+    /// Compare it with a stored value to tell whether a cached
+    /// [`Self::resolve`] result is still valid.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.layers.iter().any(|layer| layer.name == name)
+    }
+
+    /// Layers from bottom to top.
+    pub fn layers(&self) -> &[ThemeLayer] {
+        &self.layers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// The theme that results from applying every layer to `base`, bottom to top.
+    pub fn resolve(&self, base: &ThemeConfig) -> ThemeConfig {
+        self.layers.iter().fold(base.clone(), |resolved, layer| {
+            resolved.merge(&layer.config)
+        })
+    }
+
+    /// Resolves the stack on top of `base` and makes it the active theme of
+    /// `ctx` when the result differs from the current one.
+    ///
+    /// The stack is only resolved again after its layers or `base` changed,
+    /// so this is cheap to call every frame.
+    pub fn apply(&self, ctx: &egui::Context, base: &ThemeConfig) {
+        let mut cache = self.resolved.lock();
+        let stale = cache.as_ref().is_none_or(|(generation, cached_base, _)| {
+            *generation != self.generation || cached_base != base
+        });
+        if stale {
+            *cache = Some((self.generation, base.clone(), self.resolve(base)));
+        }
+        if let Some((_, _, resolved)) = cache.as_ref() {
+            ctx.set_theme_config(resolved.clone());
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// Returns `self` with every override that `overlay` sets replaced by the overlay's value.
+    ///
+    /// The name, dark mode and tags of `self` are kept.
+    pub fn merge(&self, overlay: &ThemeConfig) -> ThemeConfig {
+        let mut merged = self.clone();
+        for property in THEME_PROPERTIES {
+            if let Some(value) = property.get(overlay) {
+                let _ = property.set(&mut merged, Some(value));
+            }
+        }
+        for variant in WidgetVariant::ALL {
+            let overlay = overlay.variants.get(variant);
+            let style = merged.variants.get_mut(variant);
+            merge_variant_state(&mut style.inactive, &overlay.inactive);
+            merge_variant_state(&mut style.hovered, &overlay.hovered);
+            merge_variant_state(&mut style.active, &overlay.active);
+        }
+        merged
+            .custom_properties
+            .extend(overlay.custom_properties.clone());
+        merged
+    }
+}
+
+fn merge_variant_state(base: &mut VariantStateStyle, overlay: &VariantStateStyle) {
+    base.fill = overlay.fill.or(base.fill);
+    base.stroke_color = overlay.stroke_color.or(base.stroke_color);
+    base.stroke_width = overlay.stroke_width.or(base.stroke_width);
+    base.text = overlay.text.or(base.text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::PropertyValue;
+
+    fn overlay(fill: [u8; 4]) -> ThemeConfig {
+        ThemeConfig {
+            override_panel_fill: Some(fill),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn later_layers_win() {
+        let mut stack = ThemeStack::new();
+        stack.push("first", overlay([1, 1, 1, 255]));
+        stack.push("second", overlay([2, 2, 2, 255]));
+        let resolved = stack.resolve(&ThemeConfig::nord_preset());
+        assert_eq!(resolved.override_panel_fill, Some([2, 2, 2, 255]));
+
+        // Replacing a layer keeps its place in the stack.
+        stack.push("first", overlay([3, 3, 3, 255]));
+        let resolved = stack.resolve(&ThemeConfig::nord_preset());
+        assert_eq!(resolved.override_panel_fill, Some([2, 2, 2, 255]));
+
+        stack.remove("second");
+        let resolved = stack.resolve(&ThemeConfig::nord_preset());
+        assert_eq!(resolved.override_panel_fill, Some([3, 3, 3, 255]));
+    }
+
+    #[test]
+    fn merge_keeps_what_the_overlay_leaves_unset() {
+        let base = ThemeConfig::nord_preset();
+        let mut layer = ThemeConfig {
+            name: "Overlay".to_string(),
+            dark_mode: !base.dark_mode,
+            tags: vec!["overlay".to_string()],
+            ..overlay([9, 9, 9, 255])
+        };
+        layer.variants.primary.hovered.fill = Some([4, 4, 4, 255]);
+        layer
+            .custom_properties
+            .insert("brand".to_string(), PropertyValue::from([5, 5, 5, 255]));
+
+        let merged = base.merge(&layer);
+        assert_eq!(merged.name, base.name);
+        assert_eq!(merged.dark_mode, base.dark_mode);
+        assert_eq!(merged.tags, base.tags);
+        assert_eq!(merged.override_panel_fill, Some([9, 9, 9, 255]));
+        assert_eq!(merged.override_text_color, base.override_text_color);
+        assert_eq!(merged.variants.primary.hovered.fill, Some([4, 4, 4, 255]));
+        assert_eq!(
+            merged.variants.primary.hovered.text,
+            base.variants.primary.hovered.text
+        );
+        assert_eq!(
+            merged.custom_property("brand"),
+            Some(&PropertyValue::from([5, 5, 5, 255]))
+        );
+    }
+}
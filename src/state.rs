@@ -1,11 +1,45 @@
 use crate::config::ThemeConfig;
-use egui::{Color32, Visuals};
+#[cfg(target_arch = "wasm32")]
+use crate::files::FileDialogTask;
+use crate::files::{FileAction, ThemeFileDrop};
+use crate::history::{HistorySnapshot, ThemeHistory};
+use crate::palette::PaletteColor;
+use crate::registry::ThemeRegistry;
+use crate::response::{ThemeEditorEvent, ThemeEditorHooks};
+use crate::stack::ThemeStack;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 pub struct ThemeEditorState {
     pub current_config: ThemeConfig,
-    pub presets: Vec<ThemeConfig>,
-    pub selected_preset_index: Option<usize>,
+    pub registry: ThemeRegistry,
+    pub selected_theme_id: Option<String>,
+    pub history: ThemeHistory,
+    /// Runtime overlays applied on top of `current_config` without modifying it.
+    pub overlays: ThemeStack,
+    pub compare_theme_id: Option<String>,
+    /// Search text that filters the property sections by name, category or key.
+    pub property_filter: String,
+    /// Shows only properties that differ from [`Self::base_config`].
+    pub show_only_overridden: bool,
     pub show_code_export: bool,
+    /// Shows only themes with this tag in the gallery.
+    pub gallery_tag: Option<String>,
+    /// Shows the core colors and global sliders instead of every property section.
+    pub simple_mode: bool,
+    /// Callbacks run by [`crate::render_theme_editor`] after each frame.
+    pub hooks: ThemeEditorHooks,
+    /// The file the theme was last opened from or saved to. On the web this
+    /// is only the file name.
+    pub current_file: Option<PathBuf>,
+    /// Most recently opened or saved files, newest first.
+    pub recent_files: Vec<PathBuf>,
+    pub max_recent_files: usize,
+    /// Where user presets are saved after every change, see
+    /// [`Self::with_user_presets`]. On the web this is a `localStorage` key.
+    pub user_presets_path: Option<PathBuf>,
+    /// Handles theme files dropped onto the app when set. Off by default.
+    pub file_drop: Option<ThemeFileDrop>,
 
     pub storybook_checkbox: bool,
     pub storybook_radio: i32,
@@ -13,59 +47,114 @@ pub struct ThemeEditorState {
     pub storybook_text: String,
     pub storybook_combo_selected: usize,
 
-    pub temp_text_color: Color32,
-    pub temp_weak_text_color: Color32,
-    pub temp_hyperlink_color: Color32,
-    pub temp_faint_bg_color: Color32,
-    pub temp_extreme_bg_color: Color32,
-    pub temp_code_bg_color: Color32,
-    pub temp_warn_fg_color: Color32,
-    pub temp_error_fg_color: Color32,
-
-    pub temp_window_fill: Color32,
-    pub temp_window_stroke_color: Color32,
-    pub temp_panel_fill: Color32,
-    pub temp_selection_bg: Color32,
-    pub temp_selection_stroke_color: Color32,
-
-    pub temp_widget_noninteractive_bg_fill: Color32,
-    pub temp_widget_noninteractive_weak_bg_fill: Color32,
-    pub temp_widget_noninteractive_bg_stroke_color: Color32,
-    pub temp_widget_noninteractive_fg_stroke_color: Color32,
-
-    pub temp_widget_inactive_bg_fill: Color32,
-    pub temp_widget_inactive_weak_bg_fill: Color32,
-    pub temp_widget_inactive_bg_stroke_color: Color32,
-    pub temp_widget_inactive_fg_stroke_color: Color32,
-
-    pub temp_widget_hovered_bg_fill: Color32,
-    pub temp_widget_hovered_weak_bg_fill: Color32,
-    pub temp_widget_hovered_bg_stroke_color: Color32,
-    pub temp_widget_hovered_fg_stroke_color: Color32,
-
-    pub temp_widget_active_bg_fill: Color32,
-    pub temp_widget_active_weak_bg_fill: Color32,
-    pub temp_widget_active_bg_stroke_color: Color32,
-    pub temp_widget_active_fg_stroke_color: Color32,
-
-    pub temp_widget_open_bg_fill: Color32,
-    pub temp_widget_open_weak_bg_fill: Color32,
-    pub temp_widget_open_bg_stroke_color: Color32,
-    pub temp_widget_open_fg_stroke_color: Color32,
+    /// Seconds the user has to keep applied changes before they are reverted,
+    /// or `None` to apply without asking. Only used while previewing changes.
+    pub confirm_timeout: Option<f64>,
+
+    /// The theme applied to the app while changes are previewed.
+    committed: Option<ThemeConfig>,
+    confirmation: Option<ApplyConfirmation>,
+    resolved_visuals: Option<ResolvedVisuals>,
+    /// Visuals of the registered themes for the gallery miniatures, by id.
+    gallery_visuals: BTreeMap<String, ResolvedVisuals>,
+    resolved_applied: Option<ResolvedApplied>,
+    apply_pending: bool,
+    hover_preview: Option<HoverPreview>,
+    events: Vec<ThemeEditorEvent>,
+    /// The theme as last opened or saved, for detecting unsaved changes.
+    pub(crate) saved_config: ThemeConfig,
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) file_dialog: Option<FileDialogTask>,
+    pub(crate) file_error: Option<String>,
+    /// An action waiting for the user to confirm discarding unsaved changes.
+    pub(crate) discard_prompt: Option<FileAction>,
+    /// Why dropped files could not be loaded, and when the message goes away.
+    pub(crate) drop_toast: Option<(String, f64)>,
+    pub(crate) new_preset_name: String,
+    /// The user preset being renamed and the name typed so far.
+    pub(crate) preset_rename: Option<(String, String)>,
+    /// Whether each collapsible section was last seen open, by section key.
+    pub(crate) open_sections: BTreeMap<String, bool>,
+    pub(crate) scroll_offset: f32,
+    /// Set when a session was restored, so the editor reopens its sections and
+    /// scrolls back on the next frame.
+    pub(crate) restore_layout: bool,
+    /// Keys of the properties using the palette color under the pointer.
+    pub(crate) highlighted_properties: Vec<&'static str>,
+    /// The palette swatch being edited, by position, and the properties it
+    /// writes until its color picker closes.
+    pub(crate) palette_edit: Option<(usize, PaletteColor)>,
+    /// Whether a text field had keyboard focus at the end of the last frame.
+    pub(crate) was_typing: bool,
+}
+
+/// The visuals last computed from `current_config`.
+struct ResolvedVisuals {
+    source: ThemeConfig,
+    visuals: egui::Visuals,
+}
+
+/// The applied theme last computed from the committed or hovered theme and the overlays.
+struct ResolvedApplied {
+    source: ThemeConfig,
+    overlay_generation: u64,
+    config: ThemeConfig,
+}
+
+/// A preset applied to the app while its entry in a picker is hovered.
+struct HoverPreview {
+    id: String,
+    /// The egui frame the entry was last hovered in.
+    frame: u64,
+}
+
+/// Applied changes waiting for the user to keep them.
+struct ApplyConfirmation {
+    previous: ThemeConfig,
+    /// Set from the UI clock the first time the prompt is shown.
+    deadline: Option<f64>,
 }
 
 impl Default for ThemeEditorState {
     fn default() -> Self {
-        let presets = ThemeConfig::all_presets();
-        let dark_preset = presets[0].clone();
+        Self::from_registry(ThemeRegistry::default())
+    }
+}
 
-        let visuals = Visuals::dark();
+impl ThemeEditorState {
+    /// Creates an editor whose preset list is backed by `registry`.
+    ///
+    /// The first registered theme becomes the current theme.
+    pub fn from_registry(registry: ThemeRegistry) -> Self {
+        let (current_config, selected_theme_id) = match registry.entries().first() {
+            Some(entry) => (entry.config.clone(), Some(entry.id.clone())),
+            None => (ThemeConfig::default(), None),
+        };
+
+        let saved_config = current_config.clone();
+        let history = ThemeHistory::new(HistorySnapshot {
+            config: current_config.clone(),
+            selected_theme_id: selected_theme_id.clone(),
+        });
 
         Self {
-            current_config: dark_preset,
-            presets,
-            selected_preset_index: Some(0),
+            current_config,
+            registry,
+            selected_theme_id,
+            history,
+            overlays: ThemeStack::default(),
+            compare_theme_id: None,
+            property_filter: String::new(),
+            show_only_overridden: false,
             show_code_export: false,
+            gallery_tag: None,
+            simple_mode: false,
+            hooks: ThemeEditorHooks::default(),
+            current_file: None,
+            recent_files: Vec::new(),
+            max_recent_files: Self::DEFAULT_MAX_RECENT_FILES,
+            user_presets_path: None,
+            file_drop: None,
 
             storybook_checkbox: true,
             storybook_radio: 1,
@@ -73,105 +162,353 @@ impl Default for ThemeEditorState {
             storybook_text: "Example text".to_string(),
             storybook_combo_selected: 0,
 
-            temp_text_color: visuals.text_color(),
-            temp_weak_text_color: visuals.weak_text_color.unwrap_or(visuals.text_color()),
-            temp_hyperlink_color: visuals.hyperlink_color,
-            temp_faint_bg_color: visuals.faint_bg_color,
-            temp_extreme_bg_color: visuals.extreme_bg_color,
-            temp_code_bg_color: visuals.code_bg_color,
-            temp_warn_fg_color: visuals.warn_fg_color,
-            temp_error_fg_color: visuals.error_fg_color,
-
-            temp_window_fill: visuals.window_fill,
-            temp_window_stroke_color: visuals.window_stroke.color,
-            temp_panel_fill: visuals.panel_fill,
-            temp_selection_bg: visuals.selection.bg_fill,
-            temp_selection_stroke_color: visuals.selection.stroke.color,
-
-            temp_widget_noninteractive_bg_fill: visuals.widgets.noninteractive.bg_fill,
-            temp_widget_noninteractive_weak_bg_fill: visuals.widgets.noninteractive.weak_bg_fill,
-            temp_widget_noninteractive_bg_stroke_color: visuals
-                .widgets
-                .noninteractive
-                .bg_stroke
-                .color,
-            temp_widget_noninteractive_fg_stroke_color: visuals
-                .widgets
-                .noninteractive
-                .fg_stroke
-                .color,
-
-            temp_widget_inactive_bg_fill: visuals.widgets.inactive.bg_fill,
-            temp_widget_inactive_weak_bg_fill: visuals.widgets.inactive.weak_bg_fill,
-            temp_widget_inactive_bg_stroke_color: visuals.widgets.inactive.bg_stroke.color,
-            temp_widget_inactive_fg_stroke_color: visuals.widgets.inactive.fg_stroke.color,
-
-            temp_widget_hovered_bg_fill: visuals.widgets.hovered.bg_fill,
-            temp_widget_hovered_weak_bg_fill: visuals.widgets.hovered.weak_bg_fill,
-            temp_widget_hovered_bg_stroke_color: visuals.widgets.hovered.bg_stroke.color,
-            temp_widget_hovered_fg_stroke_color: visuals.widgets.hovered.fg_stroke.color,
-
-            temp_widget_active_bg_fill: visuals.widgets.active.bg_fill,
-            temp_widget_active_weak_bg_fill: visuals.widgets.active.weak_bg_fill,
-            temp_widget_active_bg_stroke_color: visuals.widgets.active.bg_stroke.color,
-            temp_widget_active_fg_stroke_color: visuals.widgets.active.fg_stroke.color,
-
-            temp_widget_open_bg_fill: visuals.widgets.open.bg_fill,
-            temp_widget_open_weak_bg_fill: visuals.widgets.open.weak_bg_fill,
-            temp_widget_open_bg_stroke_color: visuals.widgets.open.bg_stroke.color,
-            temp_widget_open_fg_stroke_color: visuals.widgets.open.fg_stroke.color,
+            confirm_timeout: None,
+
+            committed: None,
+            confirmation: None,
+            resolved_visuals: None,
+            gallery_visuals: BTreeMap::new(),
+            resolved_applied: None,
+            apply_pending: false,
+            hover_preview: None,
+            events: Vec::new(),
+            saved_config,
+            #[cfg(target_arch = "wasm32")]
+            file_dialog: None,
+            file_error: None,
+            discard_prompt: None,
+            drop_toast: None,
+            new_preset_name: String::new(),
+            preset_rename: None,
+            open_sections: BTreeMap::new(),
+            scroll_offset: 0.0,
+            restore_layout: false,
+            highlighted_properties: Vec::new(),
+            palette_edit: None,
+            was_typing: false,
         }
     }
-}
 
-impl ThemeEditorState {
-    pub fn reset_temp_colors(&mut self) {
-        let visuals = if self.current_config.dark_mode {
-            Visuals::dark()
-        } else {
-            Visuals::light()
+    /// Replaces the current theme with the registered theme `id`.
+    ///
+    /// Returns `false` if no theme with that id is registered.
+    pub fn select_theme(&mut self, id: &str) -> bool {
+        let Some(entry) = self.registry.get(id) else {
+            return false;
+        };
+        self.current_config = entry.config.clone();
+        self.selected_theme_id = Some(entry.id.clone());
+        true
+    }
+
+    /// Queues an event for the next [`crate::ThemeEditorResponse`].
+    pub(crate) fn emit(&mut self, event: ThemeEditorEvent) {
+        self.events.push(event);
+    }
+
+    pub(crate) fn take_events(&mut self) -> Vec<ThemeEditorEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// The theme edits are compared against and reset to: the selected registry
+    /// theme, or the plain dark/light defaults when no registry theme is selected.
+    pub fn base_config(&self) -> ThemeConfig {
+        match self
+            .selected_theme_id
+            .as_deref()
+            .and_then(|id| self.registry.get(id))
+        {
+            Some(entry) => entry.config.clone(),
+            None => ThemeConfig {
+                name: if self.current_config.dark_mode {
+                    "Dark".to_string()
+                } else {
+                    "Light".to_string()
+                },
+                dark_mode: self.current_config.dark_mode,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Default for [`Self::confirm_timeout`] when the editor's checkbox turns it on.
+    pub const DEFAULT_CONFIRM_TIMEOUT: f64 = 15.0;
+
+    /// Whether edits are previewed and only reach the app through [`Self::apply_changes`].
+    pub fn preview_changes(&self) -> bool {
+        self.committed.is_some()
+    }
+
+    /// Switches between previewing edits and applying them immediately.
+    ///
+    /// Turning previewing off applies any pending changes.
+    pub fn set_preview_changes(&mut self, preview: bool) {
+        if preview == self.preview_changes() {
+            return;
+        }
+        self.committed = preview.then(|| self.current_config.clone());
+        self.confirmation = None;
+    }
+
+    /// The theme the app uses: `current_config` unless edits are being previewed.
+    pub fn committed_config(&self) -> &ThemeConfig {
+        self.committed.as_ref().unwrap_or(&self.current_config)
+    }
+
+    pub fn has_pending_changes(&self) -> bool {
+        self.committed
+            .as_ref()
+            .is_some_and(|committed| *committed != self.current_config)
+    }
+
+    /// Applies the previewed theme to the app.
+    ///
+    /// With a [`Self::confirm_timeout`] the previous theme comes back unless
+    /// [`Self::keep_applied_changes`] is called in time.
+    pub fn apply_changes(&mut self) {
+        if !self.has_pending_changes() {
+            return;
+        }
+        let Some(committed) = &mut self.committed else {
+            return;
+        };
+        let previous = std::mem::replace(committed, self.current_config.clone());
+        if self.confirm_timeout.is_some() {
+            // Keep the oldest theme if changes are applied again while waiting.
+            let previous = match self.confirmation.take() {
+                Some(confirmation) => confirmation.previous,
+                None => previous,
+            };
+            self.confirmation = Some(ApplyConfirmation {
+                previous,
+                deadline: None,
+            });
+        }
+        self.emit(ThemeEditorEvent::Applied);
+    }
+
+    /// Discards the previewed edits, going back to the committed theme.
+    pub fn revert_changes(&mut self) {
+        if !self.has_pending_changes() {
+            return;
+        }
+        if let Some(committed) = &self.committed {
+            self.current_config = committed.clone();
+        }
+        self.record_change("Revert changes");
+        self.emit(ThemeEditorEvent::Reverted);
+    }
+
+    /// Whether applied changes are waiting to be kept or reverted.
+    pub fn is_awaiting_confirmation(&self) -> bool {
+        self.confirmation.is_some()
+    }
+
+    pub fn keep_applied_changes(&mut self) {
+        self.confirmation = None;
+    }
+
+    /// Goes back to the theme from before the last [`Self::apply_changes`].
+    pub fn revert_applied_changes(&mut self) {
+        let Some(confirmation) = self.confirmation.take() else {
+            return;
         };
+        self.current_config = confirmation.previous.clone();
+        if self.committed.is_some() {
+            self.committed = Some(confirmation.previous);
+        }
+        self.record_change("Revert applied changes");
+        self.emit(ThemeEditorEvent::Reverted);
+    }
+
+    /// Seconds left before applied changes are reverted, starting the countdown
+    /// at `now` if it has not started yet.
+    pub(crate) fn confirmation_remaining(&mut self, now: f64) -> Option<f64> {
+        let timeout = self.confirm_timeout?;
+        let confirmation = self.confirmation.as_mut()?;
+        let deadline = *confirmation.deadline.get_or_insert(now + timeout);
+        Some((deadline - now).max(0.0))
+    }
+
+    /// Makes the current theme the committed one, for when it was replaced outside the editor.
+    pub(crate) fn commit_current(&mut self) {
+        if self.committed.is_some() {
+            self.committed = Some(self.current_config.clone());
+        }
+        self.confirmation = None;
+    }
+
+    /// The theme applied to the app: the committed theme, or the preset being
+    /// hovered in a picker, with all overlays resolved on top.
+    pub fn applied_config(&self) -> ThemeConfig {
+        match &self.resolved_applied {
+            Some(resolved) if self.is_applied_current(resolved) => resolved.config.clone(),
+            _ => self.overlays.resolve(self.applied_base()),
+        }
+    }
+
+    /// The registry theme applied to the app while its picker entry is hovered.
+    pub fn hovered_preset(&self) -> Option<&str> {
+        self.hover_preview.as_ref().map(|hover| hover.id.as_str())
+    }
+
+    /// Applies the registry theme `id` to the app until it stops being hovered.
+    /// The current theme, its history and the committed theme are left alone.
+    pub(crate) fn hover_preset(&mut self, id: &str, frame: u64) {
+        self.hover_preview = Some(HoverPreview {
+            id: id.to_string(),
+            frame,
+        });
+    }
+
+    /// Ends the hover preview once a whole frame passed without a hovered entry.
+    pub(crate) fn expire_hover_preview(&mut self, frame: u64) {
+        if self
+            .hover_preview
+            .as_ref()
+            .is_some_and(|hover| hover.frame + 1 < frame)
+        {
+            self.hover_preview = None;
+        }
+    }
+
+    fn applied_base(&self) -> &ThemeConfig {
+        self.hover_preview
+            .as_ref()
+            .and_then(|hover| self.registry.get(&hover.id))
+            .map(|entry| &entry.config)
+            .unwrap_or_else(|| self.committed_config())
+    }
+
+    /// The visuals of `current_config`, recomputed only after it changes.
+    pub fn visuals(&mut self) -> &egui::Visuals {
+        self.current_with_visuals().1
+    }
+
+    /// Splits the state into the editable theme and its cached visuals, so rows
+    /// can read resolved values while writing overrides.
+    pub fn current_with_visuals(&mut self) -> (&mut ThemeConfig, &egui::Visuals) {
+        if self
+            .resolved_visuals
+            .as_ref()
+            .is_none_or(|resolved| resolved.source != self.current_config)
+        {
+            self.resolved_visuals = Some(ResolvedVisuals {
+                source: self.current_config.clone(),
+                visuals: self.current_config.to_visuals(),
+            });
+        }
+        let resolved = self
+            .resolved_visuals
+            .as_ref()
+            .expect("the visuals cache was just filled");
+        (&mut self.current_config, &resolved.visuals)
+    }
+
+    /// Recomputes the gallery visuals of registered themes that changed since
+    /// the last call and drops those of removed themes.
+    pub(crate) fn refresh_gallery_visuals(&mut self) {
+        let entries = self.registry.entries();
+        self.gallery_visuals
+            .retain(|id, _| entries.iter().any(|entry| &entry.id == id));
+        for entry in entries {
+            let stale = self
+                .gallery_visuals
+                .get(&entry.id)
+                .is_none_or(|resolved| resolved.source != entry.config);
+            if stale {
+                self.gallery_visuals.insert(
+                    entry.id.clone(),
+                    ResolvedVisuals {
+                        source: entry.config.clone(),
+                        visuals: entry.config.to_visuals(),
+                    },
+                );
+            }
+        }
+    }
+
+    pub(crate) fn gallery_visuals(&self, id: &str) -> Option<&egui::Visuals> {
+        self.gallery_visuals
+            .get(id)
+            .map(|resolved| &resolved.visuals)
+    }
+
+    /// The applied theme if it changed since the last call, for pushing to the context.
+    pub(crate) fn take_applied_change(&mut self) -> Option<ThemeConfig> {
+        if self
+            .resolved_applied
+            .as_ref()
+            .is_none_or(|resolved| !self.is_applied_current(resolved))
+        {
+            let config = self.overlays.resolve(self.applied_base());
+            self.apply_pending |= self
+                .resolved_applied
+                .as_ref()
+                .is_none_or(|resolved| resolved.config != config);
+            self.resolved_applied = Some(ResolvedApplied {
+                source: self.applied_base().clone(),
+                overlay_generation: self.overlays.generation(),
+                config,
+            });
+        }
+        if !std::mem::take(&mut self.apply_pending) {
+            return None;
+        }
+        self.resolved_applied
+            .as_ref()
+            .map(|resolved| resolved.config.clone())
+    }
+
+    fn is_applied_current(&self, resolved: &ResolvedApplied) -> bool {
+        resolved.overlay_generation == self.overlays.generation()
+            && resolved.source == *self.applied_base()
+    }
+
+    pub fn snapshot(&self) -> HistorySnapshot {
+        HistorySnapshot {
+            config: self.current_config.clone(),
+            selected_theme_id: self.selected_theme_id.clone(),
+        }
+    }
+
+    /// Records the current theme as the result of a discrete, named change.
+    pub fn record_change(&mut self, label: impl Into<String>) {
+        let snapshot = self.snapshot();
+        self.history.record(label, snapshot);
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.history.undo() {
+            Some(snapshot) => {
+                self.restore_snapshot(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.history.redo() {
+            Some(snapshot) => {
+                self.restore_snapshot(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forgets all undo/redo steps, keeping the current theme.
+    pub fn clear_history(&mut self) {
+        let snapshot = self.snapshot();
+        self.history.clear(snapshot);
+    }
 
-        self.temp_text_color = visuals.text_color();
-        self.temp_weak_text_color = visuals.weak_text_color.unwrap_or(visuals.text_color());
-        self.temp_hyperlink_color = visuals.hyperlink_color;
-        self.temp_faint_bg_color = visuals.faint_bg_color;
-        self.temp_extreme_bg_color = visuals.extreme_bg_color;
-        self.temp_code_bg_color = visuals.code_bg_color;
-        self.temp_warn_fg_color = visuals.warn_fg_color;
-        self.temp_error_fg_color = visuals.error_fg_color;
-
-        self.temp_window_fill = visuals.window_fill;
-        self.temp_window_stroke_color = visuals.window_stroke.color;
-        self.temp_panel_fill = visuals.panel_fill;
-        self.temp_selection_bg = visuals.selection.bg_fill;
-        self.temp_selection_stroke_color = visuals.selection.stroke.color;
-
-        self.temp_widget_noninteractive_bg_fill = visuals.widgets.noninteractive.bg_fill;
-        self.temp_widget_noninteractive_weak_bg_fill = visuals.widgets.noninteractive.weak_bg_fill;
-        self.temp_widget_noninteractive_bg_stroke_color =
-            visuals.widgets.noninteractive.bg_stroke.color;
-        self.temp_widget_noninteractive_fg_stroke_color =
-            visuals.widgets.noninteractive.fg_stroke.color;
-
-        self.temp_widget_inactive_bg_fill = visuals.widgets.inactive.bg_fill;
-        self.temp_widget_inactive_weak_bg_fill = visuals.widgets.inactive.weak_bg_fill;
-        self.temp_widget_inactive_bg_stroke_color = visuals.widgets.inactive.bg_stroke.color;
-        self.temp_widget_inactive_fg_stroke_color = visuals.widgets.inactive.fg_stroke.color;
-
-        self.temp_widget_hovered_bg_fill = visuals.widgets.hovered.bg_fill;
-        self.temp_widget_hovered_weak_bg_fill = visuals.widgets.hovered.weak_bg_fill;
-        self.temp_widget_hovered_bg_stroke_color = visuals.widgets.hovered.bg_stroke.color;
-        self.temp_widget_hovered_fg_stroke_color = visuals.widgets.hovered.fg_stroke.color;
-
-        self.temp_widget_active_bg_fill = visuals.widgets.active.bg_fill;
-        self.temp_widget_active_weak_bg_fill = visuals.widgets.active.weak_bg_fill;
-        self.temp_widget_active_bg_stroke_color = visuals.widgets.active.bg_stroke.color;
-        self.temp_widget_active_fg_stroke_color = visuals.widgets.active.fg_stroke.color;
-
-        self.temp_widget_open_bg_fill = visuals.widgets.open.bg_fill;
-        self.temp_widget_open_weak_bg_fill = visuals.widgets.open.weak_bg_fill;
-        self.temp_widget_open_bg_stroke_color = visuals.widgets.open.bg_stroke.color;
-        self.temp_widget_open_fg_stroke_color = visuals.widgets.open.fg_stroke.color;
+    /// Restores a history step. A preset deleted since then is not selected again.
+    fn restore_snapshot(&mut self, snapshot: HistorySnapshot) {
+        self.current_config = snapshot.config;
+        self.selected_theme_id = snapshot
+            .selected_theme_id
+            .filter(|id| self.registry.contains(id));
     }
 }
@@ -1,10 +1,22 @@
+use crate::config::ThemeConfig;
+use crate::context::ThemeContextExt;
+use crate::diff::PropertyChange;
+use crate::files::{FileAction, ThemeFileDrop};
+use crate::properties::{PropertyCategory, PropertyKind, ThemeProperty};
+use crate::registry::ThemeSource;
+use crate::response::{ThemeEditorEvent, ThemeEditorResponse};
+use crate::scope::{themed_window_frame, with_theme};
+use crate::simple::{CoreColor, GlobalControl};
 use crate::state::ThemeEditorState;
+use crate::value::PropertyValue;
+use crate::variants::{ResolvedVariantState, VariantStateStyle, VariantUiExt, WidgetVariant};
+use std::collections::BTreeMap;
 
 /// Renders the theme editor UI.
 ///
 /// This function displays a complete theme editor interface including:
 /// - Preset selector dropdown
-/// - Save/Load theme buttons
+/// - Open/Save/Save As buttons with recent files and an unsaved changes marker
 /// - Reset to Dark/Light buttons
 /// - Randomize theme button
 /// - Live preview panel
@@ -13,78 +25,343 @@ use crate::state::ThemeEditorState;
 /// Call this function inside an `egui::Window` or other container.
 /// The theme is applied immediately as changes are made.
 ///
+/// Returns what changed this frame; the same information is passed to any
+/// [`crate::ThemeEditorHooks`] set on the state.
+///
 /// # Example
 ///
 /// ```rust,ignore
 /// egui::Window::new("Theme Editor")
 ///     .show(ctx, |ui| {
-///         render_theme_editor(ui, &mut theme_editor_state);
+///         if render_theme_editor(ui, &mut theme_editor_state).changed {
+///             save_settings(&theme_editor_state.current_config);
+///         }
 ///     });
 /// ```
 pub fn render_theme_panel(
     ctx: &egui::Context,
     editor_state: &mut ThemeEditorState,
     show_theme_editor: &mut bool,
-) {
-    let visuals = editor_state.current_config.to_visuals();
-    ctx.set_visuals(visuals);
-    ctx.request_repaint();
-
-    if *show_theme_editor {
-        egui::Window::new("Theme Editor")
-            .open(show_theme_editor)
-            .resizable(true)
-            .default_width(400.0)
-            .show(ctx, |ui| {
-                render_theme_editor(ui, editor_state);
+) -> ThemeEditorResponse {
+    editor_state.expire_hover_preview(ctx.cumulative_frame_nr());
+    if editor_state.hovered_preset().is_some() {
+        // Revert the preview on the frame after the pointer leaves the entry.
+        ctx.request_repaint();
+    }
+    render_file_drop(ctx, editor_state);
+    if let Some(applied) = editor_state.take_applied_change() {
+        ctx.set_theme_config(applied);
+    }
+
+    render_apply_confirmation(ctx, editor_state);
+    if !*show_theme_editor {
+        return ThemeEditorResponse::default();
+    }
+    render_theme_preview(ctx, editor_state);
+    egui::Window::new("Theme Editor")
+        .open(show_theme_editor)
+        .resizable(true)
+        .default_width(400.0)
+        .show(ctx, |ui| render_theme_editor(ui, editor_state))
+        .and_then(|window| window.inner)
+        .unwrap_or_default()
+}
+
+/// Seconds an error about dropped files stays on screen.
+const DROP_TOAST_SECONDS: f64 = 5.0;
+
+/// Handles theme files dropped onto the app when [`ThemeEditorState::file_drop`]
+/// is set.
+///
+/// Shows a drop zone while files are dragged over the window, loads the
+/// dropped themes and shows a toast for files that are not valid themes.
+/// [`render_theme_panel`] calls this for you.
+pub fn render_file_drop(ctx: &egui::Context, editor_state: &mut ThemeEditorState) {
+    let Some(action) = editor_state.file_drop else {
+        return;
+    };
+    let (hovering, dropped, now) = ctx.input(|input| {
+        (
+            !input.raw.hovered_files.is_empty(),
+            input.raw.dropped_files.clone(),
+            input.time,
+        )
+    });
+
+    if !dropped.is_empty() {
+        let errors = editor_state.handle_dropped_files(&dropped);
+        if !errors.is_empty() {
+            editor_state.drop_toast = Some((errors.join("\n"), now + DROP_TOAST_SECONDS));
+        }
+    }
+
+    if hovering {
+        let message = match action {
+            ThemeFileDrop::Apply => "Drop a theme file to apply it",
+            ThemeFileDrop::AddToRegistry => "Drop a theme file to add it to the presets",
+        };
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("theme_file_drop"),
+        ));
+        let visuals = ctx.style().visuals.clone();
+        let rect = ctx.content_rect();
+        painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(160));
+        painter.rect_stroke(
+            rect.shrink(16.0),
+            8.0,
+            egui::Stroke::new(2.0, visuals.selection.bg_fill),
+            egui::StrokeKind::Inside,
+        );
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            message,
+            egui::FontId::proportional(24.0),
+            egui::Color32::WHITE,
+        );
+    }
+
+    let Some((message, until)) = &editor_state.drop_toast else {
+        return;
+    };
+    if now >= *until {
+        editor_state.drop_toast = None;
+        return;
+    }
+    ctx.request_repaint_after(std::time::Duration::from_secs_f64(until - now));
+    let mut dismissed = false;
+    egui::Area::new(egui::Id::new("theme_file_drop_toast"))
+        .order(egui::Order::Foreground)
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(ui.visuals().error_fg_color, message);
+                    dismissed = ui.small_button("✖").clicked();
+                });
+            });
+        });
+    if dismissed {
+        editor_state.drop_toast = None;
+    }
+}
+
+const PREVIEW_WINDOW_ID: &str = "egui_thematic_theme_preview";
+
+/// Renders the "Theme Preview" window while edits are previewed, see
+/// [`ThemeEditorState::set_preview_changes`].
+///
+/// The window uses the edited theme while the rest of the app keeps the
+/// committed one, and has the Apply and Revert buttons.
+/// [`render_theme_panel`] calls this for you.
+pub fn render_theme_preview(ctx: &egui::Context, editor_state: &mut ThemeEditorState) {
+    if !editor_state.preview_changes() {
+        return;
+    }
+    let mut style = (*ctx.style()).clone();
+    style.visuals = editor_state.visuals().clone();
+    let frame = egui::Frame::window(&style);
+
+    egui::Window::new("Theme Preview")
+        .id(egui::Id::new(PREVIEW_WINDOW_ID))
+        .frame(frame)
+        .resizable(true)
+        .default_width(320.0)
+        .show(ctx, |ui| {
+            ui.set_style(style.clone());
+            ui.label("Regular text");
+            ui.weak("Weak text");
+            ui.hyperlink_to("Hyperlink", "https://example.com");
+            ui.add_space(8.0);
+            render_storybook_widgets(ui, editor_state);
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                render_apply_buttons(ui, editor_state);
+            });
+        });
+}
+
+/// Renders the "keep these changes?" prompt after previewed changes were
+/// applied with a [`ThemeEditorState::confirm_timeout`], and reverts them
+/// when the time runs out.
+///
+/// The prompt uses the built-in dark theme so it stays readable whatever was
+/// applied. [`render_theme_panel`] calls this for you.
+pub fn render_apply_confirmation(ctx: &egui::Context, editor_state: &mut ThemeEditorState) {
+    let now = ctx.input(|input| input.time);
+    let Some(remaining) = editor_state.confirmation_remaining(now) else {
+        return;
+    };
+    if remaining <= 0.0 {
+        editor_state.revert_applied_changes();
+        return;
+    }
+    ctx.request_repaint_after(std::time::Duration::from_millis(200));
+
+    let theme = ThemeConfig::dark_preset();
+    let mut keep = false;
+    let mut revert = false;
+    egui::Window::new("Keep these theme changes?")
+        .frame(themed_window_frame(ctx, &theme))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            with_theme(ui, &theme, |ui| {
+                ui.label(format!(
+                    "The previous theme comes back in {} seconds.",
+                    remaining.ceil() as u32
+                ));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    keep = ui.button("Keep Changes").clicked();
+                    revert = ui.button("Revert").clicked();
+                });
             });
+        });
+    if keep {
+        editor_state.keep_applied_changes();
+    } else if revert {
+        editor_state.revert_applied_changes();
+    }
+}
+
+fn render_apply_buttons(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    let pending = editor_state.has_pending_changes();
+    if ui
+        .add_enabled(pending, egui::Button::new("✔ Apply"))
+        .on_hover_text("Ctrl+Enter")
+        .clicked()
+    {
+        editor_state.apply_changes();
+    }
+    if ui
+        .add_enabled(pending, egui::Button::new("✖ Revert"))
+        .on_hover_text("Escape")
+        .clicked()
+    {
+        editor_state.revert_changes();
+    }
+}
+
+fn handle_apply_shortcut(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    if !editor_state.has_pending_changes() {
+        return;
+    }
+    let apply_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Enter);
+    if ui.input_mut(|input| input.consume_shortcut(&apply_shortcut)) {
+        editor_state.apply_changes();
+    }
+}
+
+/// Reverts pending changes on Escape, once the editor's widgets have had their
+/// turn: Escape that closes a popup or leaves a text field is left alone, and
+/// so is Escape while another window is on top.
+fn handle_revert_shortcut(ui: &egui::Ui, editor_state: &mut ThemeEditorState) {
+    let ctx = ui.ctx();
+    // Focus is dropped on Escape before any widget runs, so look at last frame.
+    let was_typing = std::mem::replace(&mut editor_state.was_typing, ctx.wants_keyboard_input());
+    if was_typing
+        || !editor_state.has_pending_changes()
+        || ctx.is_popup_open()
+        || !ui.input(|input| input.key_pressed(egui::Key::Escape))
+    {
+        return;
+    }
+    let preview = egui::LayerId::new(egui::Order::Middle, egui::Id::new(PREVIEW_WINDOW_ID));
+    let focused = ctx
+        .top_layer_id()
+        .is_none_or(|top| top == ui.layer_id() || top == preview);
+    if focused {
+        editor_state.revert_changes();
     }
 }
 
-pub fn render_theme_editor(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    egui::ScrollArea::vertical().show(ui, |ui| {
+pub fn render_theme_editor(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+) -> ThemeEditorResponse {
+    let before = editor_state.current_config.clone();
+    handle_history_shortcuts(ui, editor_state);
+    handle_apply_shortcut(ui, editor_state);
+
+    let mut scroll_area = egui::ScrollArea::vertical();
+    if std::mem::take(&mut editor_state.restore_layout) {
+        scroll_area = scroll_area.vertical_scroll_offset(editor_state.scroll_offset);
+        SectionStates::restore(ui.ctx(), editor_state.open_sections.clone());
+    }
+    let scroll = scroll_area.show(ui, |ui| {
         ui.heading("🎨 Theme Studio");
+        ui.add_space(8.0);
+        render_palette_strip(ui, editor_state);
         ui.add_space(12.0);
 
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(editor_state.history.can_undo(), egui::Button::new("⟲ Undo"))
+                .on_hover_text("Ctrl+Z")
+                .clicked()
+                && editor_state.undo()
+            {
+                editor_state.emit(ThemeEditorEvent::Undone);
+            }
+            if ui
+                .add_enabled(editor_state.history.can_redo(), egui::Button::new("⟳ Redo"))
+                .on_hover_text("Ctrl+Shift+Z")
+                .clicked()
+                && editor_state.redo()
+            {
+                editor_state.emit(ThemeEditorEvent::Redone);
+            }
+
+            ui.add_space(20.0);
+
+            if ui.button("🎲 Randomize").clicked() {
+                editor_state.current_config = ThemeConfig::randomize();
+                editor_state.selected_theme_id = None;
+                editor_state.record_change("Randomize");
+                editor_state.emit(ThemeEditorEvent::Randomized);
+            }
+        });
+
+        ui.add_space(8.0);
+        render_file_controls(ui, editor_state);
+
+        ui.add_space(8.0);
+
         ui.horizontal(|ui| {
             ui.label("Theme:");
             ui.text_edit_singleline(&mut editor_state.current_config.name);
 
             ui.add_space(20.0);
 
-            if ui
-                .checkbox(&mut editor_state.current_config.dark_mode, "Dark Mode")
-                .changed()
-            {
-                editor_state.reset_temp_colors();
-            }
+            ui.checkbox(&mut editor_state.current_config.dark_mode, "Dark Mode");
 
             ui.add_space(20.0);
 
-            let selected_text = editor_state
-                .selected_preset_index
-                .and_then(|index| editor_state.presets.get(index))
-                .map(|preset| preset.name.clone())
-                .unwrap_or_else(|| "Custom".to_string());
+            let clicked_id = egui::ComboBox::from_label("Preset")
+                .selected_text(selected_preset_name(editor_state))
+                .show_ui(ui, |ui| render_preset_menu(ui, editor_state))
+                .inner
+                .flatten();
+            if let Some(id) = clicked_id {
+                select_preset(editor_state, id);
+            }
 
-            egui::ComboBox::from_label("Preset")
-                .selected_text(selected_text)
-                .show_ui(ui, |ui| {
-                    for (index, preset) in editor_state.presets.clone().iter().enumerate() {
-                        if ui
-                            .selectable_value(
-                                &mut editor_state.selected_preset_index,
-                                Some(index),
-                                &preset.name,
-                            )
-                            .clicked()
-                        {
-                            editor_state.current_config = preset.clone();
-                            editor_state.current_config = preset.clone();
-                            editor_state.reset_temp_colors();
-                        }
-                    }
-                });
+            let selected_id = editor_state.selected_theme_id.clone();
+            if ui
+                .add_enabled(selected_id.is_some(), egui::Button::new("⧉ Duplicate"))
+                .on_hover_text("Copy the selected preset into a new user preset")
+                .clicked()
+            {
+                if let Some(copy) = selected_id.and_then(|id| editor_state.duplicate_preset(&id)) {
+                    editor_state.select_theme(&copy);
+                    let label = format!("Duplicate preset '{}'", editor_state.current_config.name);
+                    editor_state.record_change(label);
+                }
+            }
 
             ui.add_space(20.0);
 
@@ -97,62 +374,53 @@ pub fn render_theme_editor(ui: &mut egui::Ui, editor_state: &mut ThemeEditorStat
         ui.separator();
         ui.add_space(12.0);
 
-        render_text_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
-
-        render_button_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
-
-        render_text_input_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
-
-        render_slider_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
-
-        render_checkbox_radio_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
-
-        render_dropdown_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
-
-        render_selection_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut editor_state.simple_mode, true, "Simple")
+                .on_hover_text("Core colors and global sliders");
+            ui.selectable_value(&mut editor_state.simple_mode, false, "Advanced")
+                .on_hover_text("Every property, grouped by section");
 
-        render_window_panel_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
+            ui.add_space(20.0);
 
-        render_shape_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
+            let mut preview = editor_state.preview_changes();
+            if ui
+                .checkbox(&mut preview, "Preview before applying")
+                .on_hover_text("Edit in a preview window and apply to the app when ready")
+                .changed()
+            {
+                editor_state.set_preview_changes(preview);
+            }
+            if preview {
+                let mut confirm = editor_state.confirm_timeout.is_some();
+                if ui
+                    .checkbox(&mut confirm, "Ask to keep")
+                    .on_hover_text("Revert applied changes unless they are confirmed in time")
+                    .changed()
+                {
+                    editor_state.confirm_timeout =
+                        confirm.then_some(ThemeEditorState::DEFAULT_CONFIRM_TIMEOUT);
+                }
+                render_apply_buttons(ui, editor_state);
+            }
+        });
+        ui.add_space(8.0);
 
-        render_special_colors_section(ui, editor_state);
-        ui.add_space(12.0);
-        ui.separator();
-        ui.add_space(12.0);
+        if editor_state.simple_mode {
+            let view = PropertyView::new(editor_state);
+            render_simple_mode(ui, editor_state, &view);
+        } else {
+            render_property_sections(ui, editor_state);
+        }
 
-        render_ui_options_section(ui, editor_state);
+        section_header(ui, "gallery", "🖼 Gallery")
+            .show(ui, |ui| render_gallery_cards(ui, editor_state));
+        render_user_presets_section(ui, editor_state);
+        render_history_section(ui, editor_state);
         ui.add_space(12.0);
         ui.separator();
         ui.add_space(12.0);
 
-        render_advanced_widget_states_section(ui, editor_state);
+        render_compare_section(ui, editor_state);
         ui.add_space(12.0);
         ui.separator();
         ui.add_space(12.0);
@@ -180,642 +448,1007 @@ ctx.set_visuals(theme.to_visuals());
             ui.add_space(12.0);
         }
     });
+    editor_state.scroll_offset = scroll.state.offset.y;
+    handle_revert_shortcut(ui, editor_state);
+    editor_state
+        .open_sections
+        .extend(SectionStates::take_open(ui.ctx()));
+
+    let continuous = ui.input(|input| input.pointer.any_down()) || ui.ctx().wants_keyboard_input();
+    editor_state.history.track(
+        &editor_state.current_config,
+        editor_state.selected_theme_id.as_deref(),
+        continuous,
+    );
+
+    let events = editor_state.take_events();
+    let response = ThemeEditorResponse::between(&before, &editor_state.current_config, events);
+    editor_state
+        .hooks
+        .run(&editor_state.current_config, &response);
+    response
 }
 
-fn render_text_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("📝 Text");
-    ui.add_space(8.0);
-
-    ui.columns(2, |columns| {
-        columns[0].vertical(|ui| {
-            ui.strong("Example:");
-            ui.add_space(4.0);
-            ui.group(|ui| {
-                ui.set_min_width(ui.available_width());
-                ui.label("Regular text");
-                ui.weak("Weak text");
-                ui.hyperlink_to("Hyperlink", "https://example.com");
-            });
-        });
+/// The open state of the editor's collapsible sections by key, passed through
+/// egui memory so sections rendered while the state is borrowed can report it.
+#[derive(Clone, Default)]
+struct SectionStates {
+    open: BTreeMap<String, bool>,
+    /// States from a restored session, applied as each section is next shown.
+    restore: BTreeMap<String, bool>,
+}
 
-        columns[1].vertical(|ui| {
-            ui.strong("Properties:");
-            ui.add_space(4.0);
+impl SectionStates {
+    fn id() -> egui::Id {
+        egui::Id::new("egui_thematic_sections")
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Text Color:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_text_color = visuals.text_color();
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_text_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_text_color =
-                        Some(editor_state.temp_text_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_text_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+    fn restore(ctx: &egui::Context, sections: BTreeMap<String, bool>) {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<SectionStates>(Self::id())
+                .restore = sections;
+        });
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Weak Text:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_weak_text_color =
-                    visuals.weak_text_color.unwrap_or(visuals.text_color());
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_weak_text_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_weak_text_color =
-                        Some(editor_state.temp_weak_text_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_weak_text_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+    fn take_open(ctx: &egui::Context) -> BTreeMap<String, bool> {
+        ctx.data_mut(|data| {
+            std::mem::take(
+                &mut data
+                    .get_temp_mut_or_default::<SectionStates>(Self::id())
+                    .open,
+            )
+        })
+    }
+}
 
-            ui.horizontal(|ui| {
-                ui.label("Hyperlink:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_hyperlink_color = visuals.hyperlink_color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_hyperlink_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_hyperlink_color =
-                        Some(editor_state.temp_hyperlink_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_hyperlink_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-        });
+/// A collapsing header whose open state is saved with the editor session
+/// under `key`, see [`crate::ThemeEditorSession::open_sections`].
+fn section_header(
+    ui: &egui::Ui,
+    key: &str,
+    title: impl Into<egui::WidgetText>,
+) -> egui::CollapsingHeader {
+    let ctx = ui.ctx();
+    // Matches the id `CollapsingHeader::id_salt` gives the header.
+    let id = ui.make_persistent_id(egui::Id::new(key));
+    let restore = ctx.data_mut(|data| {
+        data.get_temp_mut_or_default::<SectionStates>(SectionStates::id())
+            .restore
+            .remove(key)
+    });
+    let mut state =
+        egui::collapsing_header::CollapsingState::load_with_default_open(ctx, id, false);
+    if let Some(open) = restore {
+        state.set_open(open);
+        state.store(ctx);
+    }
+    let open = state.is_open();
+    ctx.data_mut(|data| {
+        data.get_temp_mut_or_default::<SectionStates>(SectionStates::id())
+            .open
+            .insert(key.to_string(), open);
     });
+    egui::CollapsingHeader::new(title).id_salt(key)
 }
 
-fn render_button_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("🔘 Buttons");
-    ui.add_space(8.0);
+/// Renders a compact dropdown for switching between registered themes, e.g.
+/// in an app's toolbar or settings page.
+///
+/// Hovering an entry previews its theme on the app, and clicking it selects
+/// the theme as an undoable step. Both reach the app through
+/// [`render_theme_panel`], which must be called every frame, even while the
+/// editor window is closed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+///     ui.horizontal(|ui| {
+///         render_theme_switcher(ui, &mut theme_editor_state);
+///     });
+/// });
+/// ```
+pub fn render_theme_switcher(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+) -> ThemeEditorResponse {
+    let before = editor_state.current_config.clone();
+    let clicked_id = egui::ComboBox::from_id_salt("theme_switcher")
+        .selected_text(selected_preset_name(editor_state))
+        .show_ui(ui, |ui| render_preset_menu(ui, editor_state))
+        .inner
+        .flatten();
+    if let Some(id) = clicked_id {
+        select_preset(editor_state, id);
+    }
 
-    ui.columns(2, |columns| {
-        columns[0].vertical(|ui| {
-            ui.strong("Example:");
-            ui.add_space(4.0);
-            ui.group(|ui| {
-                ui.set_min_width(ui.available_width());
-                let _ = ui.button("Button");
-                ui.add_space(4.0);
-                ui.add_enabled(false, egui::Button::new("Disabled"));
-            });
-        });
+    let events = editor_state.take_events();
+    let response = ThemeEditorResponse::between(&before, &editor_state.current_config, events);
+    editor_state
+        .hooks
+        .run(&editor_state.current_config, &response);
+    response
+}
 
-        columns[1].vertical(|ui| {
-            ui.strong("Properties:");
-            ui.add_space(4.0);
+fn selected_preset_name(editor_state: &ThemeEditorState) -> String {
+    editor_state
+        .selected_theme_id
+        .as_deref()
+        .and_then(|id| editor_state.registry.get(id))
+        .map(|entry| entry.config.name.clone())
+        .unwrap_or_else(|| "Custom".to_string())
+}
 
-            ui.collapsing("Inactive (Resting)", |ui| {
-                ui.horizontal(|ui| {
-                    ui.label("Fill:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_inactive_bg_fill = visuals.widgets.inactive.bg_fill;
-                    if ui
-                        .color_edit_button_srgba(&mut editor_state.temp_widget_inactive_bg_fill)
-                        .changed()
-                    {
-                        editor_state.current_config.override_widget_inactive_bg_fill =
-                            Some(editor_state.temp_widget_inactive_bg_fill.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state.current_config.override_widget_inactive_bg_fill = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
+/// Lists the registered themes grouped by source, previewing the hovered one.
+/// Returns the id of the clicked theme.
+fn render_preset_menu(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) -> Option<String> {
+    let registry = &editor_state.registry;
+    let grouped = ThemeSource::ALL
+        .iter()
+        .filter(|source| registry.by_source(**source).next().is_some())
+        .count()
+        > 1;
+    let mut clicked_id = None;
+    let mut hovered_id = None;
+    for source in ThemeSource::ALL {
+        let mut entries = registry.by_source(source).peekable();
+        if entries.peek().is_none() {
+            continue;
+        }
+        if grouped {
+            match source {
+                ThemeSource::User => ui.weak(source.label()),
+                _ => ui
+                    .weak(format!("🔒 {}", source.label()))
+                    .on_hover_text("Read-only, duplicate to edit"),
+            };
+        }
+        for entry in entries {
+            let selected = editor_state.selected_theme_id.as_deref() == Some(&entry.id);
+            let response = ui.selectable_label(selected, &entry.config.name);
+            if response.clicked() {
+                clicked_id = Some(entry.id.clone());
+            } else if response.hovered() {
+                hovered_id = Some(entry.id.clone());
+            }
+        }
+        if grouped {
+            ui.separator();
+        }
+    }
+    if let Some(id) = hovered_id {
+        editor_state.hover_preset(&id, ui.ctx().cumulative_frame_nr());
+    }
+    clicked_id
+}
 
-                ui.horizontal(|ui| {
-                    ui.label("Border:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_inactive_bg_stroke_color =
-                        visuals.widgets.inactive.bg_stroke.color;
-                    if ui
-                        .color_edit_button_srgba(
-                            &mut editor_state.temp_widget_inactive_bg_stroke_color,
-                        )
-                        .changed()
-                    {
-                        editor_state
-                            .current_config
-                            .override_widget_inactive_bg_stroke_color =
-                            Some(editor_state.temp_widget_inactive_bg_stroke_color.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state
-                            .current_config
-                            .override_widget_inactive_bg_stroke_color = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
+/// Shows every distinct color of the theme with how many properties use it.
+///
+/// Hovering a swatch highlights its properties in the sections below, and
+/// editing it changes all of them at once.
+fn render_palette_strip(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    let edit = editor_state.palette_edit.clone();
+    let palette = {
+        let (config, visuals) = editor_state.current_with_visuals();
+        match &edit {
+            Some((index, held)) => config.palette_holding(visuals, *index, &held.properties),
+            None => config.palette(visuals),
+        }
+    };
+
+    let mut hovered = None;
+    let mut edited = None;
+    let mut open = None;
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 4.0;
+        for (index, entry) in palette.iter().enumerate() {
+            ui.vertical(|ui| {
+                // The id `color_edit_button_srgba` gives its picker.
+                let popup_id = ui.auto_id_with("popup");
+                let mut color = entry.color;
+                let response = ui.color_edit_button_srgba(&mut color);
+                if response.changed() {
+                    edited = Some((index, color));
+                }
+                if egui::Popup::is_id_open(ui.ctx(), popup_id) {
+                    open = Some(index);
+                }
+                let count = entry.properties.len();
+                let response = response.on_hover_text(format!(
+                    "Used by {count} {}:\n{}",
+                    if count == 1 { "property" } else { "properties" },
+                    entry.properties.join("\n")
+                ));
+                if response.hovered() {
+                    hovered = Some(index);
+                }
+                ui.small(count.to_string());
             });
+        }
+    });
 
-            ui.collapsing("Hovered", |ui| {
-                ui.horizontal(|ui| {
-                    ui.label("Fill:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_hovered_bg_fill = visuals.widgets.hovered.bg_fill;
-                    if ui
-                        .color_edit_button_srgba(&mut editor_state.temp_widget_hovered_bg_fill)
-                        .changed()
-                    {
-                        editor_state.current_config.override_widget_hovered_bg_fill =
-                            Some(editor_state.temp_widget_hovered_bg_fill.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state.current_config.override_widget_hovered_bg_fill = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
-
-                ui.horizontal(|ui| {
-                    ui.label("Border:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_hovered_bg_stroke_color =
-                        visuals.widgets.hovered.bg_stroke.color;
-                    if ui
-                        .color_edit_button_srgba(
-                            &mut editor_state.temp_widget_hovered_bg_stroke_color,
-                        )
-                        .changed()
-                    {
-                        editor_state
-                            .current_config
-                            .override_widget_hovered_bg_stroke_color =
-                            Some(editor_state.temp_widget_hovered_bg_stroke_color.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state
-                            .current_config
-                            .override_widget_hovered_bg_stroke_color = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
-            });
+    editor_state.highlighted_properties = hovered
+        .map(|index| palette[index].properties.clone())
+        .unwrap_or_default();
+    if edit.is_some_and(|(index, _)| open != Some(index)) {
+        editor_state.palette_edit = None;
+    }
+    if let Some((index, color)) = edited {
+        let (_, held) = editor_state
+            .palette_edit
+            .get_or_insert_with(|| (index, palette[index].clone()));
+        editor_state.current_config.set_palette_color(held, color);
+    }
+}
 
-            ui.collapsing("Active (Clicking)", |ui| {
-                ui.horizontal(|ui| {
-                    ui.label("Fill:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_active_bg_fill = visuals.widgets.active.bg_fill;
-                    if ui
-                        .color_edit_button_srgba(&mut editor_state.temp_widget_active_bg_fill)
-                        .changed()
-                    {
-                        editor_state.current_config.override_widget_active_bg_fill =
-                            Some(editor_state.temp_widget_active_bg_fill.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state.current_config.override_widget_active_bg_fill = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
+/// Selects a registered theme as an undoable step.
+fn select_preset(editor_state: &mut ThemeEditorState, id: String) {
+    if editor_state.select_theme(&id) {
+        let label = format!("Select preset '{}'", editor_state.current_config.name);
+        editor_state.record_change(label);
+        editor_state.emit(ThemeEditorEvent::PresetSelected { id });
+    }
+}
 
-                ui.horizontal(|ui| {
-                    ui.label("Border:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_active_bg_stroke_color =
-                        visuals.widgets.active.bg_stroke.color;
-                    if ui
-                        .color_edit_button_srgba(
-                            &mut editor_state.temp_widget_active_bg_stroke_color,
-                        )
-                        .changed()
-                    {
-                        editor_state
-                            .current_config
-                            .override_widget_active_bg_stroke_color =
-                            Some(editor_state.temp_widget_active_bg_stroke_color.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state
-                            .current_config
-                            .override_widget_active_bg_stroke_color = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
-            });
+/// Renders every registered theme as a card with a painted miniature, grouped
+/// into dark and light themes and filtered by [`ThemeEditorState::gallery_tag`].
+///
+/// Hovering a card previews its theme on the app and clicking it selects the
+/// theme as an undoable step. The theme editor shows the same gallery in a
+/// collapsible section.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// egui::Window::new("Themes").show(ctx, |ui| {
+///     render_theme_gallery(ui, &mut theme_editor_state);
+/// });
+/// ```
+pub fn render_theme_gallery(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+) -> ThemeEditorResponse {
+    let before = editor_state.current_config.clone();
+    render_gallery_cards(ui, editor_state);
+
+    let events = editor_state.take_events();
+    let response = ThemeEditorResponse::between(&before, &editor_state.current_config, events);
+    editor_state
+        .hooks
+        .run(&editor_state.current_config, &response);
+    response
+}
 
-            ui.collapsing("Disabled", |ui| {
-                ui.horizontal(|ui| {
-                    ui.label("Fill:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_noninteractive_bg_fill =
-                        visuals.widgets.noninteractive.bg_fill;
-                    if ui
-                        .color_edit_button_srgba(
-                            &mut editor_state.temp_widget_noninteractive_bg_fill,
-                        )
-                        .changed()
-                    {
-                        editor_state
-                            .current_config
-                            .override_widget_noninteractive_bg_fill =
-                            Some(editor_state.temp_widget_noninteractive_bg_fill.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state
-                            .current_config
-                            .override_widget_noninteractive_bg_fill = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
+fn render_gallery_cards(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    let tags = editor_state.registry.tags();
+    if !tags.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Tag:");
+            ui.selectable_value(&mut editor_state.gallery_tag, None, "All");
+            for tag in tags {
+                let label = tag.clone();
+                ui.selectable_value(&mut editor_state.gallery_tag, Some(tag), label);
+            }
+        });
+        ui.add_space(4.0);
+    }
 
-                ui.horizontal(|ui| {
-                    ui.label("Border:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_noninteractive_bg_stroke_color =
-                        visuals.widgets.noninteractive.bg_stroke.color;
-                    if ui
-                        .color_edit_button_srgba(
-                            &mut editor_state.temp_widget_noninteractive_bg_stroke_color,
-                        )
-                        .changed()
-                    {
-                        editor_state
-                            .current_config
-                            .override_widget_noninteractive_bg_stroke_color = Some(
-                            editor_state
-                                .temp_widget_noninteractive_bg_stroke_color
-                                .to_array(),
-                        );
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state
-                            .current_config
-                            .override_widget_noninteractive_bg_stroke_color = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
-            });
+    editor_state.refresh_gallery_visuals();
+    let mut clicked_id = None;
+    let mut hovered_id = None;
+    let mut shown = 0;
+    for (heading, dark_mode) in [("Dark", true), ("Light", false)] {
+        let entries = editor_state
+            .registry
+            .entries()
+            .iter()
+            .filter(|entry| entry.config.dark_mode == dark_mode)
+            .filter(|entry| {
+                editor_state
+                    .gallery_tag
+                    .as_deref()
+                    .is_none_or(|tag| entry.config.has_tag(tag))
+            })
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            continue;
+        }
+        shown += entries.len();
+
+        ui.strong(heading);
+        ui.horizontal_wrapped(|ui| {
+            for entry in entries {
+                let selected = editor_state.selected_theme_id.as_deref() == Some(&entry.id);
+                let Some(visuals) = editor_state.gallery_visuals(&entry.id) else {
+                    continue;
+                };
+                let response = render_theme_card(ui, &entry.config, visuals, selected);
+                if response.clicked() {
+                    clicked_id = Some(entry.id.clone());
+                } else if response.hovered() {
+                    hovered_id = Some(entry.id.clone());
+                }
+            }
         });
-    });
+        ui.add_space(8.0);
+    }
+    if shown == 0 {
+        ui.weak("No themes match this tag");
+    }
+
+    if let Some(id) = hovered_id {
+        editor_state.hover_preset(&id, ui.ctx().cumulative_frame_nr());
+    }
+    if let Some(id) = clicked_id {
+        select_preset(editor_state, id);
+    }
 }
 
-fn render_text_input_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("✏️ Text Input");
-    ui.add_space(8.0);
+const MINIATURE_SIZE: egui::Vec2 = egui::vec2(140.0, 90.0);
+
+fn render_theme_card(
+    ui: &mut egui::Ui,
+    config: &ThemeConfig,
+    visuals: &egui::Visuals,
+    selected: bool,
+) -> egui::Response {
+    let size = MINIATURE_SIZE + egui::vec2(0.0, 20.0);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+    if ui.is_rect_visible(rect) {
+        let miniature = egui::Rect::from_min_size(rect.min, MINIATURE_SIZE);
+        let painter = ui.painter();
+        paint_theme_miniature(painter, miniature, visuals);
+
+        let outline = if selected {
+            ui.visuals().selection.stroke
+        } else if response.hovered() {
+            ui.visuals().widgets.hovered.bg_stroke
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        painter.rect_stroke(miniature, 4.0, outline, egui::StrokeKind::Outside);
+        painter.text(
+            egui::pos2(rect.center().x, miniature.bottom() + 4.0),
+            egui::Align2::CENTER_TOP,
+            &config.name,
+            egui::FontId::proportional(12.0),
+            ui.visuals().text_color(),
+        );
+    }
+    if config.tags.is_empty() {
+        response
+    } else {
+        response.on_hover_text(config.tags.join(", "))
+    }
+}
 
-    ui.columns(2, |columns| {
-        columns[0].vertical(|ui| {
-            ui.strong("Example:");
-            ui.add_space(4.0);
-            ui.group(|ui| {
-                ui.set_min_width(ui.available_width());
-                ui.text_edit_singleline(&mut editor_state.storybook_text);
-            });
-        });
+/// Paints a panel with a window, text, widget states and accent colors in
+/// the colors of `visuals`.
+fn paint_theme_miniature(painter: &egui::Painter, rect: egui::Rect, visuals: &egui::Visuals) {
+    painter.rect_filled(rect, 4.0, visuals.panel_fill);
+
+    let window = rect.shrink(10.0);
+    painter.rect(
+        window,
+        4.0,
+        visuals.window_fill,
+        visuals.window_stroke,
+        egui::StrokeKind::Inside,
+    );
+
+    let line = |y: f32, fraction: f32, color: egui::Color32| {
+        let min = egui::pos2(window.left() + 8.0, window.top() + y);
+        let width = (window.width() - 16.0) * fraction;
+        painter.rect_filled(
+            egui::Rect::from_min_size(min, egui::vec2(width, 3.0)),
+            1.5,
+            color,
+        );
+    };
+    line(8.0, 0.7, visuals.text_color());
+    line(16.0, 0.45, visuals.weak_text_color());
+
+    let states = [
+        &visuals.widgets.inactive,
+        &visuals.widgets.hovered,
+        &visuals.widgets.active,
+    ];
+    for (index, state) in states.into_iter().enumerate() {
+        let min = egui::pos2(
+            window.left() + 8.0 + index as f32 * 36.0,
+            window.top() + 26.0,
+        );
+        let button = egui::Rect::from_min_size(min, egui::vec2(32.0, 14.0));
+        painter.rect(
+            button,
+            3.0,
+            state.weak_bg_fill,
+            state.bg_stroke,
+            egui::StrokeKind::Inside,
+        );
+        painter.rect_filled(
+            egui::Rect::from_center_size(button.center(), egui::vec2(16.0, 2.0)),
+            1.0,
+            state.fg_stroke.color,
+        );
+    }
 
-        columns[1].vertical(|ui| {
-            ui.strong("Properties:");
-            ui.add_space(4.0);
+    let accents = [
+        visuals.selection.bg_fill,
+        visuals.hyperlink_color,
+        visuals.warn_fg_color,
+        visuals.error_fg_color,
+    ];
+    for (index, color) in accents.into_iter().enumerate() {
+        let center = egui::pos2(
+            window.left() + 12.0 + index as f32 * 12.0,
+            window.bottom() - 10.0,
+        );
+        painter.circle_filled(center, 4.0, color);
+    }
+}
 
-            ui.horizontal(|ui| {
-                ui.label("Fill:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_inactive_bg_fill = visuals.widgets.inactive.bg_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_inactive_bg_fill)
-                    .changed()
-                {
-                    editor_state.current_config.override_widget_inactive_bg_fill =
-                        Some(editor_state.temp_widget_inactive_bg_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_widget_inactive_bg_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+fn render_file_controls(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    editor_state.poll_file_dialog();
+    if editor_state.is_file_dialog_open() {
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_millis(100));
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Border:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_inactive_bg_stroke_color =
-                    visuals.widgets.inactive.bg_stroke.color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_inactive_bg_stroke_color)
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_bg_stroke_color =
-                        Some(editor_state.temp_widget_inactive_bg_stroke_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_bg_stroke_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+    ui.horizontal(|ui| {
+        if ui.button("📂 Open...").clicked() {
+            editor_state.request_file_action(FileAction::Open);
+        }
 
-            ui.horizontal(|ui| {
-                ui.label("Cursor Width:");
-                let mut width = editor_state
-                    .current_config
-                    .override_text_cursor_width
-                    .unwrap_or(2.0);
-                if ui.add(egui::Slider::new(&mut width, 1.0..=5.0)).changed() {
-                    editor_state.current_config.override_text_cursor_width = Some(width);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_text_cursor_width = None;
+        let mut opened = None;
+        let mut cleared = false;
+        ui.add_enabled_ui(!editor_state.recent_files.is_empty(), |ui| {
+            ui.menu_button("🕘 Recent", |ui| {
+                for path in &editor_state.recent_files {
+                    let name = path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| path.display().to_string());
+                    if ui
+                        .button(name)
+                        .on_hover_text(path.display().to_string())
+                        .clicked()
+                    {
+                        opened = Some(path.clone());
+                    }
                 }
+                ui.separator();
+                cleared = ui.button("Clear Recent").clicked();
             });
         });
+        if let Some(path) = opened {
+            editor_state.request_file_action(FileAction::OpenRecent(path));
+        }
+        if cleared {
+            editor_state.recent_files.clear();
+        }
+
+        if ui.button("💾 Save").clicked() {
+            editor_state.save();
+        }
+        if ui.button("Save As...").clicked() {
+            editor_state.save_as_dialog();
+        }
+
+        ui.add_space(12.0);
+        let name = editor_state
+            .current_file
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Untitled".to_string());
+        let label = ui.weak(format!("📄 {name}"));
+        if let Some(path) = &editor_state.current_file {
+            label.on_hover_text(path.display().to_string());
+        }
+        if editor_state.has_unsaved_changes() {
+            ui.label("●").on_hover_text("Unsaved changes");
+        }
     });
-}
 
-fn render_slider_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("🎚️ Sliders");
-    ui.add_space(8.0);
+    if let Some(action) = editor_state.discard_prompt.clone() {
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "⚠ The theme has unsaved changes.",
+            );
+            if ui.button("Discard Changes").clicked() {
+                editor_state.run_file_action(action);
+            }
+            if ui.button("Cancel").clicked() {
+                editor_state.discard_prompt = None;
+            }
+        });
+    }
 
-    ui.columns(2, |columns| {
-        columns[0].vertical(|ui| {
-            ui.strong("Example:");
-            ui.add_space(4.0);
-            ui.group(|ui| {
-                ui.set_min_width(ui.available_width());
-                ui.add(
-                    egui::Slider::new(&mut editor_state.storybook_slider, 0.0..=100.0)
-                        .text("Value"),
-                );
-            });
+    if let Some(error) = &editor_state.file_error {
+        let mut dismissed = false;
+        ui.horizontal(|ui| {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+            dismissed = ui.small_button("✖").clicked();
         });
+        if dismissed {
+            editor_state.file_error = None;
+        }
+    }
+}
 
-        columns[1].vertical(|ui| {
-            ui.strong("Properties:");
-            ui.add_space(4.0);
+fn render_property_sections(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    render_property_filter(ui, editor_state);
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(12.0);
+
+    let view = PropertyView::new(editor_state);
+    let sections: [fn(&mut egui::Ui, &mut ThemeEditorState, &PropertyView) -> bool; 8] = [
+        render_text_section,
+        render_widgets_section,
+        render_selection_section,
+        render_variants_section,
+        render_window_panel_section,
+        render_shape_section,
+        render_special_colors_section,
+        render_ui_options_section,
+    ];
+    let mut shown = 0;
+    for section in sections {
+        if section(ui, editor_state, &view) {
+            shown += 1;
+            ui.add_space(12.0);
+            ui.separator();
+            ui.add_space(12.0);
+        }
+    }
+    if shown == 0 {
+        ui.weak("No properties match the current filter.");
+        ui.add_space(12.0);
+        ui.separator();
+        ui.add_space(12.0);
+    }
+}
 
-            ui.horizontal(|ui| {
-                ui.label("Fill:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_inactive_bg_fill = visuals.widgets.inactive.bg_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_inactive_bg_fill)
-                    .changed()
-                {
-                    editor_state.current_config.override_widget_inactive_bg_fill =
-                        Some(editor_state.temp_widget_inactive_bg_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_widget_inactive_bg_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+/// Renders the core colors and global sliders of the simple mode.
+fn render_simple_mode(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState, view: &PropertyView) {
+    let (config, visuals) = editor_state.current_with_visuals();
 
-            ui.horizontal(|ui| {
-                let mut slider_trailing_fill = editor_state
-                    .current_config
-                    .override_slider_trailing_fill
-                    .unwrap_or(false);
+    ui.heading("🎨 Core Colors");
+    ui.add_space(8.0);
+    egui::Grid::new("theme_editor_core_colors")
+        .num_columns(3)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            for color in CoreColor::ALL {
+                let keys: Vec<&str> = color.properties().iter().map(|(key, _)| *key).collect();
+                ui.label(color.label());
+                let mut value = config.core_color(visuals, color);
                 if ui
-                    .checkbox(&mut slider_trailing_fill, "Trailing Fill")
+                    .color_edit_button_srgba(&mut value)
+                    .on_hover_text(keys.join("\n"))
                     .changed()
                 {
-                    editor_state.current_config.override_slider_trailing_fill =
-                        Some(slider_trailing_fill);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_slider_trailing_fill = None;
+                    config.set_core_color(color, value);
                 }
-            });
+                render_reset_keys_button(ui, config, view, &keys);
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(12.0);
+    ui.heading("⬜ Shape");
+    ui.add_space(8.0);
+    egui::Grid::new("theme_editor_global_controls")
+        .num_columns(3)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            for control in GlobalControl::ALL {
+                render_global_control_row(ui, config, visuals, view, control);
+            }
         });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(12.0);
+}
+
+/// One grid row with a slider that sets every property of `control`.
+///
+/// While the properties hold different values the slider is disabled and
+/// shows "(mixed)"; "Unify" sets them all to the first value before the slider
+/// can be used again.
+fn render_global_control_row(
+    ui: &mut egui::Ui,
+    config: &mut ThemeConfig,
+    visuals: &egui::Visuals,
+    view: &PropertyView,
+    control: GlobalControl,
+) {
+    let keys = control.properties();
+    let mixed = config.is_global_control_mixed(control);
+    let label = ui.label(if mixed {
+        format!("{} (mixed)", control.label())
+    } else {
+        control.label().to_string()
+    });
+    if mixed {
+        let values = config
+            .global_control_values(visuals, control)
+            .iter()
+            .map(|(key, value)| format!("{key}: {value}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        label.on_hover_text(values);
+    }
+
+    let mut value = config.global_control(visuals, control);
+    let mut slider = egui::Slider::new(&mut value, control.range()).text("px");
+    if control != GlobalControl::BorderWidth {
+        slider = slider.integer();
+    }
+    if ui
+        .add_enabled(!mixed, slider)
+        .on_hover_text(keys.join("\n"))
+        .on_disabled_hover_text("The properties differ; unify them to edit them together")
+        .changed()
+    {
+        config.set_global_control(control, value);
+    }
+
+    ui.horizontal(|ui| {
+        if mixed
+            && ui
+                .small_button("Unify")
+                .on_hover_text(format!("Set every property to {value}"))
+                .clicked()
+        {
+            config.set_global_control(control, value);
+        }
+        render_reset_keys_button(ui, config, view, keys);
     });
+    ui.end_row();
 }
 
-fn render_checkbox_radio_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("☑️ Checkbox & Radio");
-    ui.add_space(8.0);
+/// A "Reset" button for a group of properties, enabled while any of them is changed.
+fn render_reset_keys_button(
+    ui: &mut egui::Ui,
+    config: &mut ThemeConfig,
+    view: &PropertyView,
+    keys: &[&str],
+) {
+    let overridden = keys
+        .iter()
+        .any(|key| config.property(key) != view.base.property(key));
+    if ui
+        .add_enabled(overridden, egui::Button::new("Reset").small())
+        .on_hover_text(format!("Reset to '{}'", view.base.name))
+        .clicked()
+    {
+        for key in keys {
+            let _ = config.set_property(key, view.base.property(key));
+        }
+    }
+}
 
-    ui.columns(2, |columns| {
-        columns[0].vertical(|ui| {
-            ui.strong("Example:");
-            ui.add_space(4.0);
-            ui.group(|ui| {
-                ui.set_min_width(ui.available_width());
-                ui.checkbox(&mut editor_state.storybook_checkbox, "Checkbox");
-                ui.add_space(4.0);
-                ui.horizontal(|ui| {
-                    ui.radio_value(&mut editor_state.storybook_radio, 0, "Option A");
-                    ui.radio_value(&mut editor_state.storybook_radio, 1, "Option B");
-                });
-            });
-        });
+fn handle_history_shortcuts(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    if ui.ctx().wants_keyboard_input() {
+        return;
+    }
 
-        columns[1].vertical(|ui| {
-            ui.strong("Properties:");
-            ui.add_space(4.0);
+    let redo_shortcut = egui::KeyboardShortcut::new(
+        egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+        egui::Key::Z,
+    );
+    let redo_alternate = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y);
+    let undo_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+
+    let (redo, undo) = ui.input_mut(|input| {
+        let redo =
+            input.consume_shortcut(&redo_shortcut) || input.consume_shortcut(&redo_alternate);
+        let undo = input.consume_shortcut(&undo_shortcut);
+        (redo, undo)
+    });
+    if redo && editor_state.redo() {
+        editor_state.emit(ThemeEditorEvent::Redone);
+    } else if undo && editor_state.undo() {
+        editor_state.emit(ThemeEditorEvent::Undone);
+    }
+}
 
+fn render_user_presets_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    section_header(ui, "user_presets", "⭐ User Presets").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut editor_state.new_preset_name)
+                    .hint_text("Preset name")
+                    .desired_width(160.0),
+            );
+            let name = editor_state.new_preset_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("💾 Save as Preset"))
+                .clicked()
+            {
+                editor_state.create_user_preset(&name);
+                editor_state.new_preset_name.clear();
+            }
+        });
+        ui.add_space(4.0);
+
+        let presets = editor_state
+            .registry
+            .by_source(ThemeSource::User)
+            .map(|entry| (entry.id.clone(), entry.config.name.clone()))
+            .collect::<Vec<_>>();
+        if presets.is_empty() {
+            ui.weak("No user presets yet");
+            return;
+        }
+
+        let last = presets.len() - 1;
+        for (index, (id, name)) in presets.into_iter().enumerate() {
             ui.horizontal(|ui| {
-                ui.label("Fill:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_inactive_bg_fill = visuals.widgets.inactive.bg_fill;
+                let renaming = editor_state
+                    .preset_rename
+                    .as_ref()
+                    .is_some_and(|(renamed, _)| *renamed == id);
+                if renaming {
+                    let mut commit = false;
+                    if let Some((_, text)) = &mut editor_state.preset_rename {
+                        let response = ui.text_edit_singleline(text);
+                        commit =
+                            response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    }
+                    if ui.button("✔").on_hover_text("Rename").clicked() || commit {
+                        if let Some((_, text)) = editor_state.preset_rename.take() {
+                            if !text.trim().is_empty() {
+                                editor_state.rename_user_preset(&id, text.trim());
+                            }
+                        }
+                    }
+                    if ui.button("✖").on_hover_text("Cancel").clicked() {
+                        editor_state.preset_rename = None;
+                    }
+                    return;
+                }
+
+                let selected = editor_state.selected_theme_id.as_deref() == Some(id.as_str());
+                let response = ui.selectable_label(selected, &name);
+                if response.clicked() {
+                    select_preset(editor_state, id.clone());
+                } else if response.hovered() {
+                    editor_state.hover_preset(&id, ui.ctx().cumulative_frame_nr());
+                }
+                if ui.add_enabled(index > 0, egui::Button::new("⬆")).clicked() {
+                    editor_state.move_user_preset(&id, -1);
+                }
                 if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_inactive_bg_fill)
-                    .changed()
+                    .add_enabled(index < last, egui::Button::new("⬇"))
+                    .clicked()
                 {
-                    editor_state.current_config.override_widget_inactive_bg_fill =
-                        Some(editor_state.temp_widget_inactive_bg_fill.to_array());
+                    editor_state.move_user_preset(&id, 1);
                 }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_widget_inactive_bg_fill = None;
-                    editor_state.reset_temp_colors();
+                if ui.button("✏").on_hover_text("Rename").clicked() {
+                    editor_state.preset_rename = Some((id.clone(), name.clone()));
+                }
+                if ui.button("⧉").on_hover_text("Duplicate").clicked() {
+                    editor_state.duplicate_preset(&id);
                 }
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("Checkmark:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_inactive_fg_stroke_color =
-                    visuals.widgets.inactive.fg_stroke.color;
                 if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_inactive_fg_stroke_color)
-                    .changed()
+                    .add_enabled(selected, egui::Button::new("⟲"))
+                    .on_hover_text("Overwrite with the current theme")
+                    .clicked()
                 {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_fg_stroke_color =
-                        Some(editor_state.temp_widget_inactive_fg_stroke_color.to_array());
+                    editor_state.update_user_preset(&id);
                 }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_fg_stroke_color = None;
-                    editor_state.reset_temp_colors();
+                if ui.button("🗑").on_hover_text("Delete").clicked() {
+                    editor_state.delete_user_preset(&id);
                 }
             });
-        });
+        }
     });
 }
 
-fn render_dropdown_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("📋 Dropdowns");
-    ui.add_space(8.0);
-
-    ui.columns(2, |columns| {
-        columns[0].vertical(|ui| {
-            ui.strong("Example:");
-            ui.add_space(4.0);
-            ui.group(|ui| {
-                ui.set_min_width(ui.available_width());
-                egui::ComboBox::from_label("ComboBox")
-                    .selected_text(format!(
-                        "Item {}",
-                        editor_state.storybook_combo_selected + 1
-                    ))
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut editor_state.storybook_combo_selected,
-                            0,
-                            "Item 1",
-                        );
-                        ui.selectable_value(
-                            &mut editor_state.storybook_combo_selected,
-                            1,
-                            "Item 2",
-                        );
-                        ui.selectable_value(
-                            &mut editor_state.storybook_combo_selected,
-                            2,
-                            "Item 3",
-                        );
-                    });
-            });
-        });
+fn render_history_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    section_header(ui, "history", "🕘 History").show(ui, |ui| {
+        let undo_count = editor_state.history.undo_entries().len();
+        let redo_count = editor_state.history.redo_entries().len();
+        if undo_count == 0 && redo_count == 0 {
+            ui.weak("No changes yet");
+            return;
+        }
 
-        columns[1].vertical(|ui| {
-            ui.strong("Properties:");
-            ui.add_space(4.0);
+        let mut undo_steps = 0;
+        let mut redo_steps = 0;
 
-            ui.collapsing("Open State", |ui| {
-                ui.horizontal(|ui| {
-                    ui.label("Fill:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_open_bg_fill = visuals.widgets.open.bg_fill;
-                    if ui
-                        .color_edit_button_srgba(&mut editor_state.temp_widget_open_bg_fill)
-                        .changed()
-                    {
-                        editor_state.current_config.override_widget_open_bg_fill =
-                            Some(editor_state.temp_widget_open_bg_fill.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state.current_config.override_widget_open_bg_fill = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
+        if ui
+            .selectable_label(undo_count == 0, "Initial state")
+            .clicked()
+        {
+            undo_steps = undo_count;
+        }
+        for (index, entry) in editor_state.history.undo_entries().iter().enumerate() {
+            let current = index + 1 == undo_count;
+            if ui.selectable_label(current, &entry.label).clicked() {
+                undo_steps = undo_count - index - 1;
+            }
+        }
+        for (index, entry) in editor_state.history.redo_entries().iter().enumerate().rev() {
+            let label = egui::RichText::new(&entry.label).weak();
+            if ui.selectable_label(false, label).clicked() {
+                redo_steps = redo_count - index;
+            }
+        }
 
-                ui.horizontal(|ui| {
-                    ui.label("Border:");
-                    let visuals = editor_state.current_config.to_visuals();
-                    editor_state.temp_widget_open_bg_stroke_color =
-                        visuals.widgets.open.bg_stroke.color;
-                    if ui
-                        .color_edit_button_srgba(&mut editor_state.temp_widget_open_bg_stroke_color)
-                        .changed()
-                    {
-                        editor_state
-                            .current_config
-                            .override_widget_open_bg_stroke_color =
-                            Some(editor_state.temp_widget_open_bg_stroke_color.to_array());
-                    }
-                    if ui.small_button("Reset").clicked() {
-                        editor_state
-                            .current_config
-                            .override_widget_open_bg_stroke_color = None;
-                        editor_state.reset_temp_colors();
-                    }
-                });
-            });
-        });
+        for _ in 0..undo_steps {
+            editor_state.undo();
+        }
+        for _ in 0..redo_steps {
+            editor_state.redo();
+        }
+        if undo_steps > 0 {
+            editor_state.emit(ThemeEditorEvent::Undone);
+        }
+        if redo_steps > 0 {
+            editor_state.emit(ThemeEditorEvent::Redone);
+        }
     });
 }
 
-fn render_selection_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("🖱️ Selection");
-    ui.add_space(8.0);
+/// What the property sections show, driven by the search box and the
+/// "only changed" toggle.
+struct PropertyView {
+    query: String,
+    only_overridden: bool,
+    /// Keys of the properties using the hovered palette color.
+    highlighted: Vec<&'static str>,
+    /// The theme rows are compared against and reset to.
+    base: ThemeConfig,
+}
 
-    ui.columns(2, |columns| {
-        columns[0].vertical(|ui| {
-            ui.strong("Example:");
-            ui.add_space(4.0);
-            ui.group(|ui| {
-                ui.set_min_width(ui.available_width());
-                ui.horizontal(|ui| {
-                    let _ = ui.selectable_label(true, "Selected");
-                    let _ = ui.selectable_label(false, "Unselected");
-                });
-            });
-        });
+impl PropertyView {
+    fn new(editor_state: &ThemeEditorState) -> Self {
+        Self {
+            query: editor_state.property_filter.trim().to_lowercase(),
+            only_overridden: editor_state.show_only_overridden,
+            highlighted: editor_state.highlighted_properties.clone(),
+            base: editor_state.base_config(),
+        }
+    }
 
-        columns[1].vertical(|ui| {
-            ui.strong("Properties:");
-            ui.add_space(4.0);
+    fn is_filtering(&self) -> bool {
+        !self.query.is_empty() || self.only_overridden
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Fill:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_selection_bg = visuals.selection.bg_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_selection_bg)
-                    .changed()
-                {
-                    editor_state.current_config.override_selection_bg =
-                        Some(editor_state.temp_selection_bg.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_selection_bg = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+    fn is_overridden(&self, property: &ThemeProperty, config: &ThemeConfig) -> bool {
+        property.get(config) != property.get(&self.base)
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Border Color:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_selection_stroke_color = visuals.selection.stroke.color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_selection_stroke_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_selection_stroke_color =
-                        Some(editor_state.temp_selection_stroke_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_selection_stroke_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+    fn matches(&self, property: &ThemeProperty, config: &ThemeConfig) -> bool {
+        if self.only_overridden && !self.is_overridden(property, config) {
+            return false;
+        }
+        self.matches_query(&[
+            property.name,
+            property.key,
+            property.field,
+            property.category.label(),
+        ])
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Border Width:");
-                let mut width = editor_state
-                    .current_config
-                    .override_selection_stroke_width
-                    .unwrap_or(1.0);
-                if ui.add(egui::Slider::new(&mut width, 0.0..=5.0)).changed() {
-                    editor_state.current_config.override_selection_stroke_width = Some(width);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_selection_stroke_width = None;
-                }
-            });
-        });
+    /// Whether a section without registry rows should be shown.
+    fn matches_section(&self, terms: &[&str]) -> bool {
+        !self.only_overridden && self.matches_query(terms)
+    }
+
+    fn matches_query(&self, terms: &[&str]) -> bool {
+        self.query.is_empty()
+            || terms
+                .iter()
+                .any(|term| term.to_lowercase().contains(&self.query))
+    }
+
+    fn reset(&self, property: &ThemeProperty, config: &mut ThemeConfig) {
+        let _ = property.set(config, property.get(&self.base));
+    }
+}
+
+fn render_property_filter(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    ui.horizontal(|ui| {
+        ui.label("🔍");
+        ui.add(
+            egui::TextEdit::singleline(&mut editor_state.property_filter)
+                .hint_text("Search properties, e.g. selection stroke")
+                .desired_width(220.0),
+        );
+        if !editor_state.property_filter.is_empty() && ui.small_button("✖").clicked() {
+            editor_state.property_filter.clear();
+        }
+
+        ui.add_space(12.0);
+        ui.checkbox(&mut editor_state.show_only_overridden, "Only changed")
+            .on_hover_text("Show only properties that differ from the selected preset");
+
+        ui.add_space(12.0);
+        let base = editor_state.base_config();
+        if ui
+            .button("↺ Reset All")
+            .on_hover_text(format!("Reset every property to '{}'", base.name))
+            .clicked()
+        {
+            editor_state.current_config = base;
+            editor_state.record_change("Reset all");
+            editor_state.emit(ThemeEditorEvent::Reset);
+        }
     });
 }
 
-fn render_window_panel_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("🪟 Windows & Panels");
+/// Renders a section heading with a "Reset Section" button, returning whether it was clicked.
+fn render_section_heading(ui: &mut egui::Ui, title: &str, view: &PropertyView) -> bool {
+    ui.horizontal(|ui| {
+        ui.heading(title);
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.small_button("Reset Section")
+                .on_hover_text(format!("Reset to '{}'", view.base.name))
+                .clicked()
+        })
+        .inner
+    })
+    .inner
+}
+
+fn render_text_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    render_property_section(
+        ui,
+        editor_state,
+        view,
+        PropertyCategory::Text,
+        "📝",
+        |ui, _, _| {
+            ui.label("Regular text");
+            ui.weak("Weak text");
+            ui.hyperlink_to("Hyperlink", "https://example.com");
+        },
+    )
+}
+
+fn render_widgets_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    let states = [
+        PropertyCategory::InactiveWidgets,
+        PropertyCategory::HoveredWidgets,
+        PropertyCategory::ActiveWidgets,
+        PropertyCategory::OpenWidgets,
+        PropertyCategory::NoninteractiveWidgets,
+    ];
+    let config = &editor_state.current_config;
+    let visible = states.map(|category| {
+        category
+            .properties()
+            .filter(|property| view.matches(property, config))
+            .collect::<Vec<_>>()
+    });
+    if visible.iter().all(Vec::is_empty) {
+        return false;
+    }
+
+    if render_section_heading(ui, "🔘 Widgets", view) {
+        for category in states {
+            for property in category.properties() {
+                view.reset(property, &mut editor_state.current_config);
+            }
+        }
+        editor_state.record_change("Reset Widgets");
+    }
     ui.add_space(8.0);
 
     ui.columns(2, |columns| {
@@ -824,8 +1457,7 @@ fn render_window_panel_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditor
             ui.add_space(4.0);
             ui.group(|ui| {
                 ui.set_min_width(ui.available_width());
-                ui.label("This is inside a group");
-                ui.label("(mimics window/panel)");
+                render_storybook_widgets(ui, editor_state);
             });
         });
 
@@ -833,185 +1465,173 @@ fn render_window_panel_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditor
             ui.strong("Properties:");
             ui.add_space(4.0);
 
-            ui.horizontal(|ui| {
-                ui.label("Window Fill:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_window_fill = visuals.window_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_window_fill)
-                    .changed()
-                {
-                    editor_state.current_config.override_window_fill =
-                        Some(editor_state.temp_window_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_window_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("Panel Fill:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_panel_fill = visuals.panel_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_panel_fill)
-                    .changed()
-                {
-                    editor_state.current_config.override_panel_fill =
-                        Some(editor_state.temp_panel_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_panel_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("Border:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_window_stroke_color = visuals.window_stroke.color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_window_stroke_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_window_stroke_color =
-                        Some(editor_state.temp_window_stroke_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_window_stroke_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("Shadow Size:");
-                let shadow = editor_state
-                    .current_config
-                    .override_window_shadow_size
-                    .unwrap_or(16);
-                let mut shadow_f32 = shadow as f32;
-                if ui
-                    .add(egui::Slider::new(&mut shadow_f32, 0.0..=50.0))
-                    .changed()
-                {
-                    editor_state.current_config.override_window_shadow_size =
-                        Some(shadow_f32 as u8);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_window_shadow_size = None;
-                }
-            });
+            let (config, visuals) = editor_state.current_with_visuals();
+            for (category, properties) in states.iter().zip(visible) {
+                if properties.is_empty() {
+                    continue;
+                }
+                let open = view.is_filtering().then_some(true);
+                let key = category.label().to_lowercase();
+                section_header(ui, &key, category.label())
+                    .open(open)
+                    .show(ui, |ui| {
+                        if view.is_filtering() {
+                            render_property_rows(ui, config, visuals, view, properties);
+                            return;
+                        }
+                        let (advanced, basic): (Vec<_>, Vec<_>) = properties
+                            .into_iter()
+                            .partition(|property| property.advanced);
+                        render_property_rows(ui, config, visuals, view, basic);
+                        section_header(ui, &format!("{key}.advanced"), "Advanced").show(ui, |ui| {
+                            render_property_rows(ui, config, visuals, view, advanced);
+                        });
+                    });
+            }
         });
     });
+    true
 }
 
-fn render_shape_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("⬜ Shape & Borders");
-    ui.add_space(8.0);
+fn render_selection_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    render_property_section(
+        ui,
+        editor_state,
+        view,
+        PropertyCategory::Selection,
+        "🖱️",
+        |ui, _, _| {
+            ui.horizontal(|ui| {
+                let _ = ui.selectable_label(true, "Selected");
+                let _ = ui.selectable_label(false, "Unselected");
+            });
+        },
+    )
+}
 
-    ui.strong("Global Properties:");
-    ui.add_space(4.0);
+fn render_window_panel_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    render_property_section(
+        ui,
+        editor_state,
+        view,
+        PropertyCategory::WindowsAndPanels,
+        "🪟",
+        |ui, _, _| {
+            ui.label("This is inside a group");
+            ui.label("(mimics window/panel)");
+        },
+    )
+}
 
-    ui.horizontal(|ui| {
-        ui.label("Corner Radius:");
-        let radius = editor_state
-            .current_config
-            .override_widget_inactive_corner_radius
-            .unwrap_or(2);
-        let mut radius_f32 = radius as f32;
-        if ui
-            .add(egui::Slider::new(&mut radius_f32, 0.0..=20.0).text("px"))
-            .changed()
-        {
-            let new_radius = radius_f32 as u8;
-            editor_state
-                .current_config
-                .override_widget_inactive_corner_radius = Some(new_radius);
-            editor_state
-                .current_config
-                .override_widget_hovered_corner_radius = Some(new_radius);
-            editor_state
-                .current_config
-                .override_widget_active_corner_radius = Some(new_radius);
-            editor_state
-                .current_config
-                .override_widget_open_corner_radius = Some(new_radius);
-            editor_state
-                .current_config
-                .override_widget_noninteractive_corner_radius = Some(new_radius);
-            editor_state.current_config.override_window_corner_radius = Some(new_radius);
-        }
-        if ui.small_button("Reset").clicked() {
-            editor_state
-                .current_config
-                .override_widget_inactive_corner_radius = None;
-            editor_state
-                .current_config
-                .override_widget_hovered_corner_radius = None;
-            editor_state
-                .current_config
-                .override_widget_active_corner_radius = None;
-            editor_state
-                .current_config
-                .override_widget_open_corner_radius = None;
-            editor_state
-                .current_config
-                .override_widget_noninteractive_corner_radius = None;
-            editor_state.current_config.override_window_corner_radius = None;
-        }
-    });
+fn render_special_colors_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    render_property_section(
+        ui,
+        editor_state,
+        view,
+        PropertyCategory::SpecialColors,
+        "🎨",
+        |ui, config, visuals| {
+            ui.label("Code background");
+            ui.code("let x = 42;");
+            ui.add_space(4.0);
+            let colors = config.semantic_colors_with(visuals);
+            ui.colored_label(colors.warn_fg_color, "⚠ Warning message");
+            ui.colored_label(colors.error_fg_color, "❌ Error message");
+            ui.add_space(4.0);
+            let roles = [
+                (
+                    "✔ Success message",
+                    colors.success_fg_color,
+                    colors.success_bg_color,
+                ),
+                ("ℹ Info message", colors.info_fg_color, colors.info_bg_color),
+                (
+                    "★ Accent message",
+                    colors.accent_fg_color,
+                    colors.accent_bg_color,
+                ),
+                (
+                    "Muted message",
+                    colors.muted_fg_color,
+                    colors.muted_bg_color,
+                ),
+            ];
+            for (text, fg_color, bg_color) in roles {
+                egui::Frame::new()
+                    .fill(bg_color)
+                    .corner_radius(ui.visuals().widgets.noninteractive.corner_radius)
+                    .inner_margin(egui::Margin::symmetric(6, 2))
+                    .show(ui, |ui| {
+                        ui.colored_label(fg_color, text);
+                    });
+                ui.add_space(2.0);
+            }
+        },
+    )
+}
 
-    ui.horizontal(|ui| {
-        ui.label("Border Width:");
-        let mut width = editor_state
-            .current_config
-            .override_widget_inactive_bg_stroke_width
-            .unwrap_or(1.0);
-        if ui
-            .add(egui::Slider::new(&mut width, 0.0..=5.0).text("px"))
-            .changed()
-        {
-            editor_state
-                .current_config
-                .override_widget_inactive_bg_stroke_width = Some(width);
-            editor_state
-                .current_config
-                .override_widget_hovered_bg_stroke_width = Some(width);
-            editor_state
-                .current_config
-                .override_widget_active_bg_stroke_width = Some(width);
-            editor_state
-                .current_config
-                .override_widget_open_bg_stroke_width = Some(width);
-            editor_state
-                .current_config
-                .override_widget_noninteractive_bg_stroke_width = Some(width);
-            editor_state.current_config.override_window_stroke_width = Some(width);
-        }
-        if ui.small_button("Reset").clicked() {
-            editor_state
-                .current_config
-                .override_widget_inactive_bg_stroke_width = None;
-            editor_state
-                .current_config
-                .override_widget_hovered_bg_stroke_width = None;
-            editor_state
-                .current_config
-                .override_widget_active_bg_stroke_width = None;
-            editor_state
-                .current_config
-                .override_widget_open_bg_stroke_width = None;
-            editor_state
-                .current_config
-                .override_widget_noninteractive_bg_stroke_width = None;
-            editor_state.current_config.override_window_stroke_width = None;
-        }
-    });
+fn render_ui_options_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    render_property_section(
+        ui,
+        editor_state,
+        view,
+        PropertyCategory::UiOptions,
+        "⚙️",
+        |ui, _, _| {
+            ui.label("Various UI controls");
+            ui.add_space(4.0);
+            ui.collapsing("Collapsing", |ui| {
+                ui.label("Content");
+            });
+            ui.add_space(4.0);
+            ui.indent("indent_example", |ui| {
+                ui.label("Indented content");
+            });
+        },
+    )
 }
 
-fn render_special_colors_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("🎨 Special Colors");
+/// Renders an example next to the registry rows of `category` that match `view`.
+///
+/// Returns `false` without rendering anything when no row matches.
+fn render_property_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+    category: PropertyCategory,
+    icon: &str,
+    example: impl FnOnce(&mut egui::Ui, &ThemeConfig, &egui::Visuals),
+) -> bool {
+    let properties: Vec<_> = category
+        .properties()
+        .filter(|property| view.matches(property, &editor_state.current_config))
+        .collect();
+    if properties.is_empty() {
+        return false;
+    }
+
+    if render_section_heading(ui, &format!("{icon} {}", category.label()), view) {
+        for property in category.properties() {
+            view.reset(property, &mut editor_state.current_config);
+        }
+        editor_state.record_change(format!("Reset {}", category.label()));
+    }
     ui.add_space(8.0);
 
     ui.columns(2, |columns| {
@@ -1020,11 +1640,8 @@ fn render_special_colors_section(ui: &mut egui::Ui, editor_state: &mut ThemeEdit
             ui.add_space(4.0);
             ui.group(|ui| {
                 ui.set_min_width(ui.available_width());
-                ui.label("Code background");
-                ui.code("let x = 42;");
-                ui.add_space(4.0);
-                ui.colored_label(egui::Color32::from_rgb(255, 200, 0), "⚠ Warning message");
-                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), "❌ Error message");
+                let (config, visuals) = editor_state.current_with_visuals();
+                example(ui, config, visuals);
             });
         });
 
@@ -1032,96 +1649,151 @@ fn render_special_colors_section(ui: &mut egui::Ui, editor_state: &mut ThemeEdit
             ui.strong("Properties:");
             ui.add_space(4.0);
 
-            ui.horizontal(|ui| {
-                ui.label("Code BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_code_bg_color = visuals.code_bg_color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_code_bg_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_code_bg_color =
-                        Some(editor_state.temp_code_bg_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_code_bg_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("Faint BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_faint_bg_color = visuals.faint_bg_color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_faint_bg_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_faint_bg_color =
-                        Some(editor_state.temp_faint_bg_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_faint_bg_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+            let (config, visuals) = editor_state.current_with_visuals();
+            render_property_rows(ui, config, visuals, view, properties);
+        });
+    });
+    true
+}
 
-            ui.horizontal(|ui| {
-                ui.label("Extreme BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_extreme_bg_color = visuals.extreme_bg_color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_extreme_bg_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_extreme_bg_color =
-                        Some(editor_state.temp_extreme_bg_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_extreme_bg_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
+fn render_property_rows(
+    ui: &mut egui::Ui,
+    config: &mut ThemeConfig,
+    visuals: &egui::Visuals,
+    view: &PropertyView,
+    properties: Vec<&ThemeProperty>,
+) {
+    for property in properties {
+        render_property_row(ui, config, visuals, view, property);
+    }
+}
 
-            ui.horizontal(|ui| {
-                ui.label("Warning:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_warn_fg_color = visuals.warn_fg_color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_warn_fg_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_warn_fg_color =
-                        Some(editor_state.temp_warn_fg_color.to_array());
+fn render_property_row(
+    ui: &mut egui::Ui,
+    config: &mut ThemeConfig,
+    visuals: &egui::Visuals,
+    view: &PropertyView,
+    property: &ThemeProperty,
+) {
+    // Reserved before the row so the highlight is painted behind it.
+    let background = ui.painter().add(egui::Shape::Noop);
+    let row = ui.horizontal(|ui| {
+        let overridden = view.is_overridden(property, config);
+        if overridden {
+            ui.colored_label(ui.visuals().hyperlink_color, "●")
+                .on_hover_text(format!("Changed from '{}'", view.base.name));
+        }
+        let label = |text: String| {
+            let text = egui::RichText::new(text);
+            if overridden {
+                text.strong()
+            } else {
+                text
+            }
+        };
+
+        let value = property.resolve(config, visuals);
+        let mut edited = None;
+        let response = match property.kind {
+            PropertyKind::Color => {
+                ui.label(label(format!("{}:", property.name)));
+                let mut color = value.as_color32().unwrap_or_default();
+                let response = ui.color_edit_button_srgba(&mut color);
+                if response.changed() {
+                    edited = Some(PropertyValue::from(color));
+                }
+                response
+            }
+            PropertyKind::Float | PropertyKind::Integer => {
+                ui.label(label(format!("{}:", property.name)));
+                let (min, max) = property.range.unwrap_or((0.0, 100.0));
+                let mut number = value.as_f32().unwrap_or_default();
+                let mut slider = egui::Slider::new(&mut number, min..=max);
+                if property.kind == PropertyKind::Integer {
+                    slider = slider.integer();
+                }
+                let response = ui.add(slider);
+                if response.changed() {
+                    edited = Some(match property.kind {
+                        PropertyKind::Integer => PropertyValue::Integer(number.round() as i64),
+                        _ => PropertyValue::Float(number),
+                    });
                 }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_warn_fg_color = None;
-                    editor_state.reset_temp_colors();
+                response
+            }
+            PropertyKind::Bool => {
+                let mut checked = value.as_bool().unwrap_or_default();
+                let response = ui.checkbox(&mut checked, label(property.name.to_string()));
+                if response.changed() {
+                    edited = Some(PropertyValue::Bool(checked));
                 }
-            });
+                response
+            }
+        };
+        response.on_hover_text(property.key);
+        if let Some(value) = edited {
+            let _ = property.set(config, Some(value));
+        }
+        if ui
+            .add_enabled(overridden, egui::Button::new("Reset").small())
+            .on_hover_text(format!("Reset to '{}'", view.base.name))
+            .clicked()
+        {
+            view.reset(property, config);
+        }
+    });
+    if view.highlighted.contains(&property.key) {
+        let fill = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+        ui.painter().set(
+            background,
+            egui::Shape::rect_filled(row.response.rect.expand(2.0), 2.0, fill),
+        );
+    }
+}
 
-            ui.horizontal(|ui| {
-                ui.label("Error:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_error_fg_color = visuals.error_fg_color;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_error_fg_color)
-                    .changed()
-                {
-                    editor_state.current_config.override_error_fg_color =
-                        Some(editor_state.temp_error_fg_color.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_error_fg_color = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-        });
+/// Sample widgets bound to the editor's storybook fields.
+fn render_storybook_widgets(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    let _ = ui.button("Button");
+    ui.add_space(4.0);
+    ui.add_enabled(false, egui::Button::new("Disabled"));
+    ui.add_space(4.0);
+    ui.text_edit_singleline(&mut editor_state.storybook_text);
+    ui.add_space(4.0);
+    ui.add(egui::Slider::new(&mut editor_state.storybook_slider, 0.0..=100.0).text("Value"));
+    ui.add_space(4.0);
+    ui.checkbox(&mut editor_state.storybook_checkbox, "Checkbox");
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut editor_state.storybook_radio, 0, "Option A");
+        ui.radio_value(&mut editor_state.storybook_radio, 1, "Option B");
     });
+    ui.add_space(4.0);
+    egui::ComboBox::from_label("ComboBox")
+        .selected_text(format!(
+            "Item {}",
+            editor_state.storybook_combo_selected + 1
+        ))
+        .show_ui(ui, |ui| {
+            for index in 0..3 {
+                ui.selectable_value(
+                    &mut editor_state.storybook_combo_selected,
+                    index,
+                    format!("Item {}", index + 1),
+                );
+            }
+        });
 }
 
-fn render_ui_options_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.heading("⚙️ UI Options");
+fn render_variants_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    let labels = WidgetVariant::ALL.map(|variant| variant.label());
+    if !view.matches_section(&[&["Variants", "Buttons"][..], &labels[..]].concat()) {
+        return false;
+    }
+
+    ui.heading("🏷 Variants");
     ui.add_space(8.0);
 
     ui.columns(2, |columns| {
@@ -1130,14 +1802,25 @@ fn render_ui_options_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorSt
             ui.add_space(4.0);
             ui.group(|ui| {
                 ui.set_min_width(ui.available_width());
-                ui.label("Various UI controls");
-                ui.add_space(4.0);
-                ui.collapsing("Collapsing", |ui| {
-                    ui.label("Content");
-                });
+                let theme = &editor_state.current_config;
+                for variant in WidgetVariant::ALL {
+                    ui.horizontal(|ui| {
+                        let _ = ui.variant_button(theme, variant, variant.label());
+                        let toggle_id = ui.id().with(("variant_toggle", variant));
+                        let mut selected =
+                            ui.data(|data| data.get_temp(toggle_id)).unwrap_or(false);
+                        if ui
+                            .variant_toggle(theme, variant, &mut selected, "Toggle")
+                            .changed()
+                        {
+                            ui.data_mut(|data| data.insert_temp(toggle_id, selected));
+                        }
+                    });
+                    ui.add_space(2.0);
+                }
                 ui.add_space(4.0);
-                ui.indent("indent_example", |ui| {
-                    ui.label("Indented content");
+                ui.variant_frame(theme, WidgetVariant::Primary, |ui| {
+                    ui.label("Primary frame");
                 });
             });
         });
@@ -1146,421 +1829,188 @@ fn render_ui_options_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorSt
             ui.strong("Properties:");
             ui.add_space(4.0);
 
-            ui.horizontal(|ui| {
-                let mut button_frame = editor_state
-                    .current_config
-                    .override_button_frame
-                    .unwrap_or(true);
-                if ui.checkbox(&mut button_frame, "Button Frame").changed() {
-                    editor_state.current_config.override_button_frame = Some(button_frame);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_button_frame = None;
-                }
-            });
+            for variant in WidgetVariant::ALL {
+                let key = format!("variants.{}", variant.label()).to_lowercase();
+                section_header(ui, &key, variant.label()).show(ui, |ui| {
+                    let resolved = {
+                        let (config, visuals) = editor_state.current_with_visuals();
+                        config.resolve_variant_with(visuals, variant)
+                    };
+                    let style = editor_state.current_config.variants.get_mut(variant);
+                    let states = [
+                        ("Inactive", &mut style.inactive, resolved.inactive),
+                        ("Hovered", &mut style.hovered, resolved.hovered),
+                        ("Active", &mut style.active, resolved.active),
+                    ];
+                    for (label, state, resolved) in states {
+                        ui.push_id(label, |ui| {
+                            ui.label(label);
+                            render_variant_state_controls(ui, state, resolved);
+                        });
+                        ui.add_space(4.0);
+                    }
+                    if ui.small_button("Reset Variant").clicked() {
+                        *editor_state.current_config.variants.get_mut(variant) = Default::default();
+                    }
+                });
+            }
+        });
+    });
+    true
+}
 
-            ui.horizontal(|ui| {
-                let mut collapsing_header_frame = editor_state
-                    .current_config
-                    .override_collapsing_header_frame
-                    .unwrap_or(false);
-                if ui
-                    .checkbox(&mut collapsing_header_frame, "Collapsing Frame")
-                    .changed()
-                {
-                    editor_state.current_config.override_collapsing_header_frame =
-                        Some(collapsing_header_frame);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_collapsing_header_frame = None;
-                }
-            });
+fn render_variant_state_controls(
+    ui: &mut egui::Ui,
+    state: &mut VariantStateStyle,
+    resolved: ResolvedVariantState,
+) {
+    let color_rows = [
+        ("Fill:", &mut state.fill, resolved.fill),
+        ("Border:", &mut state.stroke_color, resolved.stroke.color),
+        ("Text:", &mut state.text, resolved.text),
+    ];
+    for (label, value, color) in color_rows {
+        render_color_override_row(ui, label, value, color);
+    }
 
-            ui.horizontal(|ui| {
-                let mut indent_has_left_vline = editor_state
-                    .current_config
-                    .override_indent_has_left_vline
-                    .unwrap_or(true);
-                if ui
-                    .checkbox(&mut indent_has_left_vline, "Indent Vertical Line")
-                    .changed()
-                {
-                    editor_state.current_config.override_indent_has_left_vline =
-                        Some(indent_has_left_vline);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_indent_has_left_vline = None;
-                }
-            });
+    ui.horizontal(|ui| {
+        ui.label("Border Width:");
+        let mut width = resolved.stroke.width;
+        if ui.add(egui::Slider::new(&mut width, 0.0..=5.0)).changed() {
+            state.stroke_width = Some(width);
+        }
+        if ui.small_button("Reset").clicked() {
+            state.stroke_width = None;
+        }
+    });
+}
 
-            ui.horizontal(|ui| {
-                let mut striped = editor_state
-                    .current_config
-                    .override_striped
-                    .unwrap_or(false);
-                if ui.checkbox(&mut striped, "Striped Tables").changed() {
-                    editor_state.current_config.override_striped = Some(striped);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_striped = None;
-                }
-            });
+fn render_color_override_row(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Option<[u8; 4]>,
+    mut color: egui::Color32,
+) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        if ui.color_edit_button_srgba(&mut color).changed() {
+            *value = Some(color.to_srgba_unmultiplied());
+        }
+        if ui.small_button("Reset").clicked() {
+            *value = None;
+        }
+    });
+}
 
-            ui.horizontal(|ui| {
-                ui.label("Resize Corner:");
-                let mut size = editor_state
-                    .current_config
-                    .override_resize_corner_size
-                    .unwrap_or(12.0);
-                if ui.add(egui::Slider::new(&mut size, 6.0..=30.0)).changed() {
-                    editor_state.current_config.override_resize_corner_size = Some(size);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_resize_corner_size = None;
-                }
-            });
+fn render_shape_section(
+    ui: &mut egui::Ui,
+    editor_state: &mut ThemeEditorState,
+    view: &PropertyView,
+) -> bool {
+    let controls = [GlobalControl::CornerRadius, GlobalControl::BorderWidth];
+    let labels = controls.map(|control| control.label());
+    if !view.matches_section(&[&["Shape & Borders"][..], &labels[..]].concat()) {
+        return false;
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Clip Margin:");
-                let mut margin = editor_state
-                    .current_config
-                    .override_clip_rect_margin
-                    .unwrap_or(3.0);
-                if ui.add(egui::Slider::new(&mut margin, 0.0..=10.0)).changed() {
-                    editor_state.current_config.override_clip_rect_margin = Some(margin);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_clip_rect_margin = None;
-                }
-            });
+    ui.heading("⬜ Shape & Borders");
+    ui.add_space(8.0);
 
-            ui.horizontal(|ui| {
-                ui.label("Popup Shadow:");
-                let popup_shadow = editor_state
-                    .current_config
-                    .override_popup_shadow_size
-                    .unwrap_or(16);
-                let mut popup_shadow_f32 = popup_shadow as f32;
-                if ui
-                    .add(egui::Slider::new(&mut popup_shadow_f32, 0.0..=50.0))
-                    .changed()
-                {
-                    editor_state.current_config.override_popup_shadow_size =
-                        Some(popup_shadow_f32 as u8);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_popup_shadow_size = None;
-                }
-            });
+    ui.strong("Global Properties:");
+    ui.add_space(4.0);
+
+    let (config, visuals) = editor_state.current_with_visuals();
+    egui::Grid::new("theme_editor_shape_controls")
+        .num_columns(3)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            for control in controls {
+                render_global_control_row(ui, config, visuals, view, control);
+            }
         });
-    });
+    true
 }
 
-fn render_advanced_widget_states_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
-    ui.collapsing("🔧 Advanced Per-State Controls", |ui| {
-        ui.label(
-            "Fine-tune weak fills, per-state radii/widths, and expansion for each widget state",
-        );
-        ui.add_space(8.0);
-
-        ui.collapsing("Inactive State (Advanced)", |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Weak BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_inactive_weak_bg_fill =
-                    visuals.widgets.inactive.weak_bg_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_inactive_weak_bg_fill)
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_weak_bg_fill =
-                        Some(editor_state.temp_widget_inactive_weak_bg_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_weak_bg_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("FG Stroke Width:");
-                let mut width = editor_state
-                    .current_config
-                    .override_widget_inactive_fg_stroke_width
-                    .unwrap_or(1.0);
-                if ui.add(egui::Slider::new(&mut width, 0.0..=5.0)).changed() {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_fg_stroke_width = Some(width);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_fg_stroke_width = None;
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("Expansion:");
-                let mut expansion = editor_state
-                    .current_config
-                    .override_widget_inactive_expansion
-                    .unwrap_or(0.0);
-                if ui
-                    .add(egui::Slider::new(&mut expansion, -5.0..=10.0))
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_expansion = Some(expansion);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_inactive_expansion = None;
+fn render_compare_section(ui: &mut egui::Ui, editor_state: &mut ThemeEditorState) {
+    section_header(ui, "compare", "🔍 Compare with...").show(ui, |ui| {
+        let selected_text = editor_state
+            .compare_theme_id
+            .as_deref()
+            .and_then(|id| editor_state.registry.get(id))
+            .map(|entry| entry.config.name.clone())
+            .unwrap_or_else(|| "Select a theme".to_string());
+
+        egui::ComboBox::from_id_salt("compare_theme")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for entry in editor_state.registry.entries() {
+                    ui.selectable_value(
+                        &mut editor_state.compare_theme_id,
+                        Some(entry.id.clone()),
+                        &entry.config.name,
+                    );
                 }
             });
-        });
 
-        ui.collapsing("Hovered State (Advanced)", |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Weak BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_hovered_weak_bg_fill =
-                    visuals.widgets.hovered.weak_bg_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_hovered_weak_bg_fill)
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_hovered_weak_bg_fill =
-                        Some(editor_state.temp_widget_hovered_weak_bg_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_hovered_weak_bg_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("FG Stroke Width:");
-                let mut width = editor_state
-                    .current_config
-                    .override_widget_hovered_fg_stroke_width
-                    .unwrap_or(1.0);
-                if ui.add(egui::Slider::new(&mut width, 0.0..=5.0)).changed() {
-                    editor_state
-                        .current_config
-                        .override_widget_hovered_fg_stroke_width = Some(width);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_hovered_fg_stroke_width = None;
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("Expansion:");
-                let mut expansion = editor_state
-                    .current_config
-                    .override_widget_hovered_expansion
-                    .unwrap_or(0.0);
-                if ui
-                    .add(egui::Slider::new(&mut expansion, -5.0..=10.0))
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_hovered_expansion = Some(expansion);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_hovered_expansion = None;
-                }
-            });
-        });
+        let Some(base) = editor_state
+            .compare_theme_id
+            .as_deref()
+            .and_then(|id| editor_state.registry.get(id))
+        else {
+            return;
+        };
 
-        ui.collapsing("Active State (Advanced)", |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Weak BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_active_weak_bg_fill = visuals.widgets.active.weak_bg_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_active_weak_bg_fill)
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_active_weak_bg_fill =
-                        Some(editor_state.temp_widget_active_weak_bg_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_active_weak_bg_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("FG Stroke Width:");
-                let mut width = editor_state
-                    .current_config
-                    .override_widget_active_fg_stroke_width
-                    .unwrap_or(1.0);
-                if ui.add(egui::Slider::new(&mut width, 0.0..=5.0)).changed() {
-                    editor_state
-                        .current_config
-                        .override_widget_active_fg_stroke_width = Some(width);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_active_fg_stroke_width = None;
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("Expansion:");
-                let mut expansion = editor_state
-                    .current_config
-                    .override_widget_active_expansion
-                    .unwrap_or(0.0);
-                if ui
-                    .add(egui::Slider::new(&mut expansion, -5.0..=10.0))
-                    .changed()
-                {
-                    editor_state.current_config.override_widget_active_expansion = Some(expansion);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_widget_active_expansion = None;
+        let diff = base.config.diff(&editor_state.current_config);
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(format!("{} differences", diff.len()));
+            if ui.small_button("📋 Copy changelog").clicked() {
+                ui.ctx().copy_text(diff.changelog());
+            }
+            if ui.small_button("📋 Copy patch").clicked() {
+                if let Ok(json) = diff.to_patch().to_json() {
+                    ui.ctx().copy_text(json);
                 }
-            });
+            }
         });
-
-        ui.collapsing("Open State (Advanced)", |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Weak BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_open_weak_bg_fill = visuals.widgets.open.weak_bg_fill;
-                if ui
-                    .color_edit_button_srgba(&mut editor_state.temp_widget_open_weak_bg_fill)
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_open_weak_bg_fill =
-                        Some(editor_state.temp_widget_open_weak_bg_fill.to_array());
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_open_weak_bg_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("FG Stroke Width:");
-                let mut width = editor_state
-                    .current_config
-                    .override_widget_open_fg_stroke_width
-                    .unwrap_or(1.0);
-                if ui.add(egui::Slider::new(&mut width, 0.0..=5.0)).changed() {
-                    editor_state
-                        .current_config
-                        .override_widget_open_fg_stroke_width = Some(width);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_open_fg_stroke_width = None;
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("Expansion:");
-                let mut expansion = editor_state
-                    .current_config
-                    .override_widget_open_expansion
-                    .unwrap_or(0.0);
-                if ui
-                    .add(egui::Slider::new(&mut expansion, -5.0..=10.0))
-                    .changed()
-                {
-                    editor_state.current_config.override_widget_open_expansion = Some(expansion);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state.current_config.override_widget_open_expansion = None;
+        ui.add_space(4.0);
+
+        egui::Grid::new("compare_grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("");
+                ui.strong("Property");
+                ui.strong(&base.config.name);
+                ui.strong("Current");
+                ui.end_row();
+
+                for property in &diff.properties {
+                    let (marker, color) = match property.change {
+                        PropertyChange::Added { .. } => ("+", ui.visuals().hyperlink_color),
+                        PropertyChange::Removed { .. } => ("-", ui.visuals().error_fg_color),
+                        PropertyChange::Changed { .. } => ("~", ui.visuals().warn_fg_color),
+                    };
+                    ui.colored_label(color, marker);
+                    ui.label(&property.key);
+                    render_property_value(ui, property.change.old_value());
+                    render_property_value(ui, property.change.new_value());
+                    ui.end_row();
                 }
             });
-        });
+    });
+}
 
-        ui.collapsing("Noninteractive State (Advanced)", |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Weak BG:");
-                let visuals = editor_state.current_config.to_visuals();
-                editor_state.temp_widget_noninteractive_weak_bg_fill =
-                    visuals.widgets.noninteractive.weak_bg_fill;
-                if ui
-                    .color_edit_button_srgba(
-                        &mut editor_state.temp_widget_noninteractive_weak_bg_fill,
-                    )
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_noninteractive_weak_bg_fill = Some(
-                        editor_state
-                            .temp_widget_noninteractive_weak_bg_fill
-                            .to_array(),
-                    );
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_noninteractive_weak_bg_fill = None;
-                    editor_state.reset_temp_colors();
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("FG Stroke Width:");
-                let mut width = editor_state
-                    .current_config
-                    .override_widget_noninteractive_fg_stroke_width
-                    .unwrap_or(1.0);
-                if ui.add(egui::Slider::new(&mut width, 0.0..=5.0)).changed() {
-                    editor_state
-                        .current_config
-                        .override_widget_noninteractive_fg_stroke_width = Some(width);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_noninteractive_fg_stroke_width = None;
-                }
-            });
-            ui.horizontal(|ui| {
-                ui.label("Expansion:");
-                let mut expansion = editor_state
-                    .current_config
-                    .override_widget_noninteractive_expansion
-                    .unwrap_or(0.0);
-                if ui
-                    .add(egui::Slider::new(&mut expansion, -5.0..=10.0))
-                    .changed()
-                {
-                    editor_state
-                        .current_config
-                        .override_widget_noninteractive_expansion = Some(expansion);
-                }
-                if ui.small_button("Reset").clicked() {
-                    editor_state
-                        .current_config
-                        .override_widget_noninteractive_expansion = None;
-                }
-            });
-        });
+fn render_property_value(ui: &mut egui::Ui, value: Option<&PropertyValue>) {
+    ui.horizontal(|ui| match value {
+        Some(value) => {
+            if let Some(color) = value.as_color32() {
+                egui::color_picker::show_color(ui, color, egui::vec2(16.0, 16.0));
+            }
+            ui.monospace(value.to_string());
+        }
+        None => {
+            ui.weak("default");
+        }
     });
 }
@@ -0,0 +1,141 @@
+use egui::Color32;
+
+/// A single themable value, as stored in a [`crate::ThemeConfig`] override.
+///
+/// Serializes to the same JSON shape as the config field it came from, so a
+/// color is `[r, g, b, a]`, a radius is an integer, a width is a float and the
+/// tags are a list of strings.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum PropertyValue {
+    Color([u8; 4]),
+    Integer(i64),
+    Float(f32),
+    Bool(bool),
+    Text(String),
+    List(Vec<String>),
+}
+
+impl PropertyValue {
+    pub fn as_color(&self) -> Option<[u8; 4]> {
+        match self {
+            PropertyValue::Color(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    pub fn as_color32(&self) -> Option<Color32> {
+        match self {
+            PropertyValue::Color(color) => Some(Color32::from_rgba_unmultiplied(
+                color[0], color[1], color[2], color[3],
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            PropertyValue::Float(value) => Some(*value),
+            PropertyValue::Integer(value) => Some(*value as f32),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[String]> {
+        match self {
+            PropertyValue::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_json(value: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        match self {
+            PropertyValue::Color(color) => serde_json::Value::from(color.to_vec()),
+            PropertyValue::Integer(value) => serde_json::Value::from(*value),
+            PropertyValue::Float(value) => serde_json::Value::from(*value),
+            PropertyValue::Bool(value) => serde_json::Value::from(*value),
+            PropertyValue::Text(value) => serde_json::Value::from(value.as_str()),
+            PropertyValue::List(values) => serde_json::Value::from(values.clone()),
+        }
+    }
+}
+
+impl From<Color32> for PropertyValue {
+    fn from(color: Color32) -> Self {
+        PropertyValue::Color(color.to_srgba_unmultiplied())
+    }
+}
+
+impl From<[u8; 4]> for PropertyValue {
+    fn from(color: [u8; 4]) -> Self {
+        PropertyValue::Color(color)
+    }
+}
+
+impl From<i64> for PropertyValue {
+    fn from(value: i64) -> Self {
+        PropertyValue::Integer(value)
+    }
+}
+
+impl From<f32> for PropertyValue {
+    fn from(value: f32) -> Self {
+        PropertyValue::Float(value)
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        PropertyValue::Bool(value)
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        PropertyValue::Text(value)
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        PropertyValue::Text(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for PropertyValue {
+    fn from(values: Vec<String>) -> Self {
+        PropertyValue::List(values)
+    }
+}
+
+impl std::fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyValue::Color([r, g, b, 255]) => write!(f, "#{r:02x}{g:02x}{b:02x}"),
+            PropertyValue::Color([r, g, b, a]) => write!(f, "#{r:02x}{g:02x}{b:02x}{a:02x}"),
+            PropertyValue::Integer(value) => write!(f, "{value}"),
+            PropertyValue::Float(value) => write!(f, "{value}"),
+            PropertyValue::Bool(value) => write!(f, "{value}"),
+            PropertyValue::Text(value) => write!(f, "{value:?}"),
+            PropertyValue::List(values) => write!(f, "{values:?}"),
+        }
+    }
+}
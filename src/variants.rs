@@ -0,0 +1,370 @@
+use crate::config::ThemeConfig;
+use crate::context::ThemeContextExt;
+use crate::semantic::SemanticColors;
+use egui::{Color32, Stroke, Visuals};
+
+/// A semantic look for buttons, toggles and frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WidgetVariant {
+    Primary,
+    Secondary,
+    Danger,
+    Success,
+    Ghost,
+}
+
+impl WidgetVariant {
+    pub const ALL: [WidgetVariant; 5] = [
+        WidgetVariant::Primary,
+        WidgetVariant::Secondary,
+        WidgetVariant::Danger,
+        WidgetVariant::Success,
+        WidgetVariant::Ghost,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WidgetVariant::Primary => "Primary",
+            WidgetVariant::Secondary => "Secondary",
+            WidgetVariant::Danger => "Danger",
+            WidgetVariant::Success => "Success",
+            WidgetVariant::Ghost => "Ghost",
+        }
+    }
+}
+
+/// Overrides for one interaction state of a variant. `None` uses the variant's default.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct VariantStateStyle {
+    pub fill: Option<[u8; 4]>,
+    pub stroke_color: Option<[u8; 4]>,
+    pub stroke_width: Option<f32>,
+    pub text: Option<[u8; 4]>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct VariantStyle {
+    pub inactive: VariantStateStyle,
+    pub hovered: VariantStateStyle,
+    pub active: VariantStateStyle,
+}
+
+/// Per-variant overrides stored in a [`ThemeConfig`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct ThemeVariants {
+    pub primary: VariantStyle,
+    pub secondary: VariantStyle,
+    pub danger: VariantStyle,
+    pub success: VariantStyle,
+    pub ghost: VariantStyle,
+}
+
+impl ThemeVariants {
+    pub fn get(&self, variant: WidgetVariant) -> &VariantStyle {
+        match variant {
+            WidgetVariant::Primary => &self.primary,
+            WidgetVariant::Secondary => &self.secondary,
+            WidgetVariant::Danger => &self.danger,
+            WidgetVariant::Success => &self.success,
+            WidgetVariant::Ghost => &self.ghost,
+        }
+    }
+
+    pub fn get_mut(&mut self, variant: WidgetVariant) -> &mut VariantStyle {
+        match variant {
+            WidgetVariant::Primary => &mut self.primary,
+            WidgetVariant::Secondary => &mut self.secondary,
+            WidgetVariant::Danger => &mut self.danger,
+            WidgetVariant::Success => &mut self.success,
+            WidgetVariant::Ghost => &mut self.ghost,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResolvedVariantState {
+    pub fill: Color32,
+    pub stroke: Stroke,
+    pub text: Color32,
+}
+
+/// The final colors of a variant after applying overrides to its defaults.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResolvedVariant {
+    pub inactive: ResolvedVariantState,
+    pub hovered: ResolvedVariantState,
+    pub active: ResolvedVariantState,
+}
+
+impl ResolvedVariant {
+    /// Writes the variant into the interactive widget states of `visuals`.
+    pub fn apply_to_visuals(&self, visuals: &mut Visuals) {
+        let states = [
+            (&mut visuals.widgets.inactive, self.inactive),
+            (&mut visuals.widgets.hovered, self.hovered),
+            (&mut visuals.widgets.active, self.active),
+        ];
+        for (widget, state) in states {
+            widget.bg_fill = state.fill;
+            widget.weak_bg_fill = state.fill;
+            widget.bg_stroke = state.stroke;
+            widget.fg_stroke.color = state.text;
+        }
+        visuals.selection.bg_fill = self.active.fill;
+        visuals.selection.stroke = Stroke::new(visuals.selection.stroke.width, self.active.text);
+        visuals.override_text_color = None;
+    }
+}
+
+impl ThemeConfig {
+    pub fn resolve_variant(&self, variant: WidgetVariant) -> ResolvedVariant {
+        self.resolve_variant_with(&self.to_visuals(), variant)
+    }
+
+    /// Resolves a variant against already computed visuals for this theme.
+    pub fn resolve_variant_with(
+        &self,
+        visuals: &Visuals,
+        variant: WidgetVariant,
+    ) -> ResolvedVariant {
+        let defaults = default_variant(visuals, &self.semantic_colors_with(visuals), variant);
+        let style = self.variants.get(variant);
+        ResolvedVariant {
+            inactive: resolve_state(defaults.inactive, &style.inactive),
+            hovered: resolve_state(defaults.hovered, &style.hovered),
+            active: resolve_state(defaults.active, &style.active),
+        }
+    }
+
+    /// A frame filled and outlined in the resting colors of `variant`.
+    pub fn variant_frame(&self, variant: WidgetVariant) -> egui::Frame {
+        let visuals = self.to_visuals();
+        let resolved = self.resolve_variant_with(&visuals, variant);
+        egui::Frame::new()
+            .fill(resolved.inactive.fill)
+            .stroke(resolved.inactive.stroke)
+            .corner_radius(visuals.widgets.inactive.corner_radius)
+            .inner_margin(8.0)
+    }
+}
+
+fn resolve_state(default: ResolvedVariantState, style: &VariantStateStyle) -> ResolvedVariantState {
+    let color =
+        |color: [u8; 4]| Color32::from_rgba_unmultiplied(color[0], color[1], color[2], color[3]);
+    ResolvedVariantState {
+        fill: style.fill.map(color).unwrap_or(default.fill),
+        stroke: Stroke::new(
+            style.stroke_width.unwrap_or(default.stroke.width),
+            style
+                .stroke_color
+                .map(color)
+                .unwrap_or(default.stroke.color),
+        ),
+        text: style.text.map(color).unwrap_or(default.text),
+    }
+}
+
+fn default_variant(
+    visuals: &Visuals,
+    semantic: &SemanticColors,
+    variant: WidgetVariant,
+) -> ResolvedVariant {
+    let widgets = &visuals.widgets;
+    let solid = |fill: Color32| {
+        let shift = if visuals.dark_mode {
+            Color32::WHITE
+        } else {
+            Color32::BLACK
+        };
+        let text = contrasting_text(fill);
+        ResolvedVariant {
+            inactive: ResolvedVariantState {
+                fill,
+                stroke: Stroke::NONE,
+                text,
+            },
+            hovered: ResolvedVariantState {
+                fill: fill.lerp_to_gamma(shift, 0.15),
+                stroke: Stroke::new(
+                    widgets.hovered.bg_stroke.width,
+                    fill.lerp_to_gamma(shift, 0.4),
+                ),
+                text,
+            },
+            active: ResolvedVariantState {
+                fill: fill.lerp_to_gamma(shift, 0.3),
+                stroke: Stroke::new(
+                    widgets.active.bg_stroke.width,
+                    fill.lerp_to_gamma(shift, 0.5),
+                ),
+                text,
+            },
+        }
+    };
+
+    match variant {
+        WidgetVariant::Primary => solid(visuals.selection.bg_fill),
+        WidgetVariant::Danger => solid(visuals.error_fg_color),
+        WidgetVariant::Success => solid(semantic.success_fg_color),
+        WidgetVariant::Secondary => ResolvedVariant {
+            inactive: ResolvedVariantState {
+                fill: widgets.inactive.weak_bg_fill,
+                stroke: widgets.inactive.bg_stroke,
+                text: widgets.inactive.fg_stroke.color,
+            },
+            hovered: ResolvedVariantState {
+                fill: widgets.hovered.weak_bg_fill,
+                stroke: widgets.hovered.bg_stroke,
+                text: widgets.hovered.fg_stroke.color,
+            },
+            active: ResolvedVariantState {
+                fill: widgets.active.weak_bg_fill,
+                stroke: widgets.active.bg_stroke,
+                text: widgets.active.fg_stroke.color,
+            },
+        },
+        WidgetVariant::Ghost => ResolvedVariant {
+            inactive: ResolvedVariantState {
+                fill: Color32::TRANSPARENT,
+                stroke: Stroke::NONE,
+                text: visuals.text_color(),
+            },
+            hovered: ResolvedVariantState {
+                fill: widgets.hovered.weak_bg_fill,
+                stroke: Stroke::NONE,
+                text: visuals.strong_text_color(),
+            },
+            active: ResolvedVariantState {
+                fill: widgets.active.weak_bg_fill,
+                stroke: Stroke::NONE,
+                text: visuals.strong_text_color(),
+            },
+        },
+    }
+}
+
+fn contrasting_text(fill: Color32) -> Color32 {
+    if fill.intensity() > 0.55 {
+        Color32::from_gray(20)
+    } else {
+        Color32::from_gray(245)
+    }
+}
+
+/// Variant widgets on [`egui::Ui`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use egui_thematic::{VariantUiExt, WidgetVariant};
+///
+/// if ui.variant_button(&theme, WidgetVariant::Primary, "Save").clicked() {
+///     // ...
+/// }
+/// ui.variant_button(&theme, WidgetVariant::Danger, "Delete");
+/// ui.variant_toggle(&theme, WidgetVariant::Success, &mut enabled, "Enabled");
+/// ```
+pub trait VariantUiExt {
+    fn variant_button(
+        &mut self,
+        theme: &ThemeConfig,
+        variant: WidgetVariant,
+        text: impl Into<egui::WidgetText>,
+    ) -> egui::Response;
+
+    /// A button that stays in the variant's active colors while `selected` is `true`.
+    fn variant_toggle(
+        &mut self,
+        theme: &ThemeConfig,
+        variant: WidgetVariant,
+        selected: &mut bool,
+        text: impl Into<egui::WidgetText>,
+    ) -> egui::Response;
+
+    /// Renders `add_contents` inside [`ThemeConfig::variant_frame`].
+    fn variant_frame<R>(
+        &mut self,
+        theme: &ThemeConfig,
+        variant: WidgetVariant,
+        add_contents: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> egui::InnerResponse<R>;
+}
+
+impl VariantUiExt for egui::Ui {
+    fn variant_button(
+        &mut self,
+        theme: &ThemeConfig,
+        variant: WidgetVariant,
+        text: impl Into<egui::WidgetText>,
+    ) -> egui::Response {
+        let resolved = resolve_for_ui(self, theme, variant);
+        self.scope(|ui| {
+            resolved.apply_to_visuals(ui.visuals_mut());
+            ui.add(egui::Button::new(text))
+        })
+        .inner
+    }
+
+    fn variant_toggle(
+        &mut self,
+        theme: &ThemeConfig,
+        variant: WidgetVariant,
+        selected: &mut bool,
+        text: impl Into<egui::WidgetText>,
+    ) -> egui::Response {
+        let resolved = resolve_for_ui(self, theme, variant);
+        self.scope(|ui| {
+            resolved.apply_to_visuals(ui.visuals_mut());
+            let mut response = ui.add(egui::Button::new(text).selected(*selected));
+            if response.clicked() {
+                *selected = !*selected;
+                response.mark_changed();
+            }
+            response
+        })
+        .inner
+    }
+
+    fn variant_frame<R>(
+        &mut self,
+        theme: &ThemeConfig,
+        variant: WidgetVariant,
+        add_contents: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> egui::InnerResponse<R> {
+        let resolved = resolve_for_ui(self, theme, variant);
+        let corner_radius = self.visuals().widgets.inactive.corner_radius;
+        self.scope(|ui| {
+            ui.visuals_mut().override_text_color = Some(resolved.inactive.text);
+            egui::Frame::new()
+                .fill(resolved.inactive.fill)
+                .stroke(resolved.inactive.stroke)
+                .corner_radius(corner_radius)
+                .inner_margin(8.0)
+                .show(ui, add_contents)
+                .inner
+        })
+    }
+}
+
+/// Resolves `variant` against the visuals of `theme`. The visuals come from
+/// the active theme when `theme` is active, or from the last theme resolved
+/// this way, so they are not recomputed for every widget.
+fn resolve_for_ui(ui: &egui::Ui, theme: &ThemeConfig, variant: WidgetVariant) -> ResolvedVariant {
+    if let Some(active) = ui.active_theme().filter(|active| active.config == *theme) {
+        return theme.resolve_variant_with(&active.visuals, variant);
+    }
+
+    let id = egui::Id::new("egui_thematic_variant_visuals");
+    let cached = ui
+        .data(|data| data.get_temp::<std::sync::Arc<(ThemeConfig, Visuals)>>(id))
+        .filter(|cached| cached.0 == *theme);
+    let cached = cached.unwrap_or_else(|| {
+        let cached = std::sync::Arc::new((theme.clone(), theme.to_visuals()));
+        ui.data_mut(|data| data.insert_temp(id, cached.clone()));
+        cached
+    });
+    theme.resolve_variant_with(&cached.1, variant)
+}
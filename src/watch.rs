@@ -0,0 +1,146 @@
+use crate::config::ThemeConfig;
+use crate::context::ThemeContextExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThemeReloadEvent {
+    /// The file changed and the new theme was loaded and validated.
+    Reloaded,
+    /// The file changed but could not be loaded; the last good theme is kept.
+    Failed(String),
+}
+
+/// A theme loaded from a file that is reloaded whenever the file changes.
+///
+/// Changes are detected by polling the file's modification time and size, so
+/// no platform file-watching service is required. While the file is broken the
+/// last successfully loaded theme stays active and the error is reported.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut watcher = ThemeFileWatcher::new("themes/studio.theme.json");
+///
+/// // Every frame:
+/// if let Some(ThemeReloadEvent::Failed(error)) = watcher.update(ctx) {
+///     eprintln!("theme reload failed: {error}");
+/// }
+/// ```
+pub struct ThemeFileWatcher {
+    path: PathBuf,
+    poll_interval: Duration,
+    last_poll_time: Option<f64>,
+    last_stamp: Option<(SystemTime, u64)>,
+    config: ThemeConfig,
+    last_error: Option<String>,
+    needs_apply: bool,
+}
+
+impl ThemeFileWatcher {
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Starts watching `path`, loading it immediately.
+    ///
+    /// If the initial load fails the default theme is used until the file
+    /// becomes valid, and the error is available from [`Self::last_error`].
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let mut watcher = Self {
+            path: path.into(),
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            last_poll_time: None,
+            last_stamp: None,
+            config: ThemeConfig::default(),
+            last_error: None,
+            needs_apply: true,
+        };
+        watcher.check();
+        watcher
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The last theme that loaded and validated successfully.
+    pub fn config(&self) -> &ThemeConfig {
+        &self.config
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Checks the file right away and reloads it if it changed since the last check.
+    pub fn check(&mut self) -> Option<ThemeReloadEvent> {
+        let stamp = match std::fs::metadata(&self.path) {
+            Ok(metadata) => (
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                metadata.len(),
+            ),
+            Err(error) => return self.fail(None, error.to_string()),
+        };
+        if self.last_stamp == Some(stamp) {
+            return None;
+        }
+
+        let result = ThemeConfig::load_from_file(&self.path).and_then(|config| {
+            config.validate()?;
+            Ok(config)
+        });
+        match result {
+            Ok(config) => {
+                self.last_stamp = Some(stamp);
+                self.last_error = None;
+                if config != self.config {
+                    self.config = config;
+                    self.needs_apply = true;
+                }
+                Some(ThemeReloadEvent::Reloaded)
+            }
+            Err(error) => self.fail(Some(stamp), error.to_string()),
+        }
+    }
+
+    /// Polls the file at most once per poll interval and applies the theme to
+    /// `ctx` whenever it changes.
+    pub fn update(&mut self, ctx: &egui::Context) -> Option<ThemeReloadEvent> {
+        let now = ctx.input(|input| input.time);
+        let due = self
+            .last_poll_time
+            .is_none_or(|last| now - last >= self.poll_interval.as_secs_f64());
+
+        let event = if due {
+            self.last_poll_time = Some(now);
+            self.check()
+        } else {
+            None
+        };
+
+        if self.needs_apply {
+            self.needs_apply = false;
+            ctx.set_theme_config(self.config.clone());
+        }
+        ctx.request_repaint_after(self.poll_interval);
+
+        event
+    }
+
+    fn fail(
+        &mut self,
+        stamp: Option<(SystemTime, u64)>,
+        error: String,
+    ) -> Option<ThemeReloadEvent> {
+        if self.last_error.as_deref() == Some(error.as_str()) && self.last_stamp == stamp {
+            return None;
+        }
+        self.last_stamp = stamp;
+        self.last_error = Some(error.clone());
+        Some(ThemeReloadEvent::Failed(error))
+    }
+}
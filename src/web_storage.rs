@@ -0,0 +1,76 @@
+use crate::persistence::ThemeStorage;
+
+/// The browser's `localStorage`.
+///
+/// On the web, [`crate::ThemeConfig::save_to_file`], [`crate::ThemeConfig::load_from_file`]
+/// and [`crate::ThemeRegistry::discover`] read and write here instead of the
+/// file system, so the same code works on native and web. It can also be
+/// passed to [`crate::ThemeEditorState::save_to_storage`] to remember the
+/// active theme across page reloads.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use egui_thematic::LocalStorage;
+///
+/// if let Ok(mut storage) = LocalStorage::open() {
+///     theme_editor_state.load_from_storage(&storage);
+///     // ...
+///     theme_editor_state.save_to_storage(&mut storage);
+/// }
+/// ```
+pub struct LocalStorage {
+    storage: web_sys::Storage,
+}
+
+impl LocalStorage {
+    /// Prefix of the keys that hold theme files.
+    pub const FILE_KEY_PREFIX: &'static str = "egui_thematic_file:";
+
+    pub fn open() -> std::io::Result<Self> {
+        let storage = web_sys::window()
+            .ok_or_else(|| std::io::Error::other("no browser window"))?
+            .local_storage()
+            .map_err(js_error)?
+            .ok_or_else(|| std::io::Error::other("local storage is unavailable"))?;
+        Ok(Self { storage })
+    }
+
+    pub fn get(&self, key: &str) -> std::io::Result<Option<String>> {
+        self.storage.get_item(key).map_err(js_error)
+    }
+
+    /// Fails when the browser's storage quota is exceeded.
+    pub fn set(&self, key: &str, value: &str) -> std::io::Result<()> {
+        self.storage.set_item(key, value).map_err(js_error)
+    }
+
+    pub fn remove(&self, key: &str) -> std::io::Result<()> {
+        self.storage.remove_item(key).map_err(js_error)
+    }
+
+    pub fn keys(&self) -> std::io::Result<Vec<String>> {
+        let length = self.storage.length().map_err(js_error)?;
+        let mut keys = Vec::with_capacity(length as usize);
+        for index in 0..length {
+            if let Some(key) = self.storage.key(index).map_err(js_error)? {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+}
+
+impl ThemeStorage for LocalStorage {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.get(key).ok().flatten()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        let _ = self.set(key, &value);
+    }
+}
+
+fn js_error(error: web_sys::wasm_bindgen::JsValue) -> std::io::Error {
+    std::io::Error::other(format!("{error:?}"))
+}